@@ -0,0 +1,63 @@
+use std::str::FromStr;
+
+// Minimal command line options for the day binaries, e.g. --animate --delay 200
+pub struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    pub fn new(args: Vec<String>) -> Self {
+        Args { args }
+    }
+
+    // Everything after the program name
+    pub fn from_env() -> Self {
+        Args::new(std::env::args().skip(1).collect())
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.args.iter().any(|arg| arg == name)
+    }
+
+    // The argument following `name`, if present
+    pub fn value(&self, name: &str) -> Option<&str> {
+        let index = self.args.iter().position(|arg| arg == name)?;
+        match self.args.get(index + 1) {
+            Some(value) => Some(value.as_str()),
+            None => panic!("Missing value for {}", name),
+        }
+    }
+
+    pub fn parsed<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.value(name).map(|value| {
+            value
+                .parse::<T>()
+                .unwrap_or_else(|_| panic!("Invalid value for {}: {}", name, value))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(text: &str) -> Args {
+        Args::new(text.split_whitespace().map(String::from).collect())
+    }
+
+    #[test]
+    fn test_flag_and_value() {
+        let args = args("--animate --delay 200");
+        assert!(args.flag("--animate"));
+        assert!(!args.flag("--show"));
+        assert_eq!(args.value("--delay"), Some("200"));
+        assert_eq!(args.parsed::<u64>("--delay"), Some(200));
+        assert_eq!(args.parsed::<u64>("--width"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Missing value for --delay")]
+    fn test_missing_value() {
+        args("--delay").value("--delay");
+    }
+}
@@ -0,0 +1,272 @@
+use std::io::Write;
+use std::path::Path;
+
+pub type Rgb = [u8; 3];
+
+// Largest payload of a stored (uncompressed) deflate block
+const MAX_STORED_BLOCK: usize = 65535;
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+// Palette indexed image, written out as 8-bit RGB
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<u8>,
+    palette: Vec<Rgb>,
+}
+
+impl Image {
+    // Every pixel starts as palette entry 0
+    pub fn new(width: usize, height: usize, palette: Vec<Rgb>) -> Self {
+        assert!(!palette.is_empty(), "Palette must have at least one colour");
+        Image {
+            width,
+            height,
+            pixels: vec![0; width * height],
+            palette,
+        }
+    }
+
+    // Build an image by asking for the palette index of every pixel
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        palette: Vec<Rgb>,
+        index: impl Fn(usize, usize) -> u8,
+    ) -> Self {
+        let mut image = Image::new(width, height, palette);
+        for y in 0..height {
+            for x in 0..width {
+                image.set(x, y, index(x, y));
+            }
+        }
+        image
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, index: u8) {
+        assert!(
+            (index as usize) < self.palette.len(),
+            "Palette index {} out of range",
+            index
+        );
+        self.pixels[y * self.width + x] = index;
+    }
+
+    // Each pixel becomes a factor x factor block
+    pub fn upscale(&self, factor: usize) -> Image {
+        assert!(factor > 0, "Scale factor must be at least 1");
+        Image::from_fn(
+            self.width * factor,
+            self.height * factor,
+            self.palette.clone(),
+            |x, y| self.get(x / factor, y / factor),
+        )
+    }
+
+    // Row major RGB triples
+    pub fn to_rgb(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|&index| self.palette[index as usize])
+            .collect()
+    }
+
+    // Binary PPM (P6)
+    pub fn write_ppm(&self, out: &mut impl Write) -> std::io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.to_rgb())
+    }
+
+    // PNG using stored deflate blocks, so no compression but no dependencies either
+    pub fn write_png(&self, out: &mut impl Write) -> std::io::Result<()> {
+        // PNG has no empty images, and a zero width would leave no scanlines to split into
+        if self.width == 0 || self.height == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "PNG needs at least one pixel, image is {}x{}",
+                    self.width, self.height
+                ),
+            ));
+        }
+
+        let mut header = Vec::new();
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // bit depth 8, colour type 2 (RGB), default compression, filter and interlace
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        // every scanline starts with filter type 0 (none)
+        let rgb = self.to_rgb();
+        let mut raw = Vec::with_capacity(rgb.len() + self.height);
+        for row in rgb.chunks(self.width * 3) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+
+        out.write_all(&PNG_SIGNATURE)?;
+        write_chunk(out, b"IHDR", &header)?;
+        write_chunk(out, b"IDAT", &zlib_stored(&raw))?;
+        write_chunk(out, b"IEND", &[])
+    }
+
+    // Format picked from the extension, .ppm or .png
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let mut out = Vec::new();
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ppm") => self.write_ppm(&mut out)?,
+            Some("png") => self.write_png(&mut out)?,
+            _ => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Unsupported image format: {}", path.display()),
+                ));
+            }
+        }
+        std::fs::write(path, out)
+    }
+}
+
+fn write_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> std::io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    let mut crc_input = kind.to_vec();
+    crc_input.extend_from_slice(data);
+    out.write_all(&crc32(&crc_input).to_be_bytes())
+}
+
+// zlib stream made of stored deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // deflate, 32K window, no preset dictionary, header check bits so it divides by 31
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_STORED_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        // an empty stream still needs one final block
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        out.push(if last { 1 } else { 0 });
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+pub fn adler32(data: &[u8]) -> u32 {
+    const MOD_ADLER: u32 = 65521;
+    let mut a = 1u32;
+    let mut b = 0u32;
+    for &byte in data {
+        a = (a + byte as u32) % MOD_ADLER;
+        b = (b + a) % MOD_ADLER;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: Rgb = [0, 0, 0];
+    const RED: Rgb = [255, 0, 0];
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn test_upscale() {
+        let image = Image::from_fn(2, 1, vec![BLACK, RED], |x, _| x as u8);
+        let scaled = image.upscale(2);
+        assert_eq!((scaled.width, scaled.height), (4, 2));
+        assert_eq!(scaled.get(1, 1), 0);
+        assert_eq!(scaled.get(2, 0), 1);
+        assert_eq!(scaled.get(3, 1), 1);
+    }
+
+    #[test]
+    fn test_write_ppm() {
+        let image = Image::from_fn(2, 1, vec![BLACK, RED], |x, _| x as u8);
+        let mut out = Vec::new();
+        image.write_ppm(&mut out).unwrap();
+        assert_eq!(out, b"P6\n2 1\n255\n\x00\x00\x00\xff\x00\x00");
+    }
+
+    #[test]
+    fn test_write_png() {
+        let image = Image::from_fn(1, 1, vec![RED], |_, _| 0);
+        let mut out = Vec::new();
+        image.write_png(&mut out).unwrap();
+
+        assert_eq!(&out[..8], &PNG_SIGNATURE);
+        // IHDR is always 13 bytes
+        assert_eq!(&out[8..16], b"\x00\x00\x00\x0dIHDR");
+        assert_eq!(&out[16..24], &[0, 0, 0, 1, 0, 0, 0, 1]);
+        // IDAT holds a single stored block with the filter byte and one pixel
+        let idat = &out[33..];
+        assert_eq!(&idat[..8], b"\x00\x00\x00\x0fIDAT");
+        assert_eq!(
+            &idat[8..23],
+            &[
+                0x78, 0x01, 1, 4, 0, 0xfb, 0xff, 0, 255, 0, 0, 0x03, 0x01, 0x01, 0x00
+            ]
+        );
+        assert_eq!(
+            &out[out.len() - 12..],
+            b"\x00\x00\x00\x00IEND\xae\x42\x60\x82"
+        );
+    }
+
+    #[test]
+    fn test_write_png_rejects_empty_image() {
+        for (width, height) in [(0, 0), (0, 3), (3, 0)] {
+            let image = Image::new(width, height, vec![BLACK]);
+            let mut out = Vec::new();
+            assert!(image.write_png(&mut out).is_err());
+            assert!(out.is_empty());
+            // PPM can still describe an empty image
+            image.write_ppm(&mut out).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_zlib_stored_splits_blocks() {
+        let data = vec![7u8; MAX_STORED_BLOCK + 10];
+        let stream = zlib_stored(&data);
+        // header, two block headers, data and checksum
+        assert_eq!(stream.len(), 2 + 5 + 5 + data.len() + 4);
+        assert_eq!(stream[2], 0);
+        assert_eq!(stream[2 + 5 + MAX_STORED_BLOCK], 1);
+    }
+
+    #[test]
+    fn test_save_rejects_unknown_extension() {
+        let image = Image::new(1, 1, vec![BLACK]);
+        assert!(image.save(Path::new("grid.gif")).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use std::io::{IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+// Minimum time between redraws of the progress line
+const RENDER_INTERVAL: Duration = Duration::from_millis(100);
+
+// Hook for long running loops to report how far through they are
+pub trait Progress {
+    // Set the total number of items, None if it isn't known up front
+    fn set_total(&mut self, total: Option<u64>);
+    // Record that `n` more items have been completed
+    fn advance(&mut self, n: u64);
+    // Called once the loop has finished
+    fn finish(&mut self);
+}
+
+// Progress hook that ignores everything, used by tests and quiet runs
+pub struct NoProgress;
+
+impl Progress for NoProgress {
+    fn set_total(&mut self, _total: Option<u64>) {}
+    fn advance(&mut self, _n: u64) {}
+    fn finish(&mut self) {}
+}
+
+// Renders progress as a single updating line on stderr
+pub struct ProgressBar {
+    label: String,
+    done: u64,
+    total: Option<u64>,
+    started: Instant,
+    last_render: Option<Instant>,
+    // Only redraw in place when stderr is a terminal
+    interactive: bool,
+}
+
+impl ProgressBar {
+    pub fn new(label: &str, total: Option<u64>) -> Self {
+        ProgressBar {
+            label: label.to_string(),
+            done: 0,
+            total,
+            started: Instant::now(),
+            last_render: None,
+            interactive: std::io::stderr().is_terminal(),
+        }
+    }
+
+    fn render(&mut self, now: Instant) {
+        self.last_render = Some(now);
+        let line = format_line(&self.label, self.done, self.total, now - self.started);
+        let mut stderr = std::io::stderr();
+        // \r plus clear-line so shorter lines don't leave junk behind
+        let _ = write!(stderr, "\r\x1b[2K{}", line);
+        let _ = stderr.flush();
+    }
+}
+
+impl Progress for ProgressBar {
+    fn set_total(&mut self, total: Option<u64>) {
+        self.total = total;
+    }
+
+    fn advance(&mut self, n: u64) {
+        self.done += n;
+        if !self.interactive {
+            return;
+        }
+        let now = Instant::now();
+        let due = match self.last_render {
+            Some(last) => now - last >= RENDER_INTERVAL,
+            None => true,
+        };
+        if due {
+            self.render(now);
+        }
+    }
+
+    fn finish(&mut self) {
+        let now = Instant::now();
+        if self.interactive {
+            self.render(now);
+            eprintln!();
+        } else {
+            eprintln!(
+                "{}",
+                format_line(&self.label, self.done, self.total, now - self.started)
+            );
+        }
+    }
+}
+
+// Build the text of a progress line: items done, total, rate and ETA
+pub fn format_line(label: &str, done: u64, total: Option<u64>, elapsed: Duration) -> String {
+    let secs = elapsed.as_secs_f64();
+    let rate = if secs > 0.0 { done as f64 / secs } else { 0.0 };
+
+    match total {
+        Some(total) => {
+            let percent = if total > 0 {
+                done as f64 * 100.0 / total as f64
+            } else {
+                100.0
+            };
+            let eta = if done >= total {
+                "done".to_string()
+            } else if rate > 0.0 {
+                format_duration(Duration::from_secs_f64((total - done) as f64 / rate))
+            } else {
+                "--".to_string()
+            };
+            format!(
+                "{}: {}/{} ({:.1}%) {:.0}/s ETA {}",
+                label, done, total, percent, rate, eta
+            )
+        }
+        None => format!("{}: {} {:.0}/s", label, done, rate),
+    }
+}
+
+// Format a duration compactly, e.g. 1h02m03s, 4m05s or 7s
+pub fn format_duration(duration: Duration) -> String {
+    let total_secs = duration.as_secs();
+    let hours = total_secs / 3600;
+    let minutes = (total_secs % 3600) / 60;
+    let seconds = total_secs % 60;
+
+    if hours > 0 {
+        format!("{}h{:02}m{:02}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m{:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(7)), "7s");
+        assert_eq!(format_duration(Duration::from_secs(245)), "4m05s");
+        assert_eq!(format_duration(Duration::from_secs(3723)), "1h02m03s");
+    }
+
+    #[test]
+    fn test_format_line_with_total() {
+        let line = format_line("Scanning", 250, Some(1000), Duration::from_secs(5));
+        assert_eq!(line, "Scanning: 250/1000 (25.0%) 50/s ETA 15s");
+
+        let line = format_line("Scanning", 1000, Some(1000), Duration::from_secs(5));
+        assert_eq!(line, "Scanning: 1000/1000 (100.0%) 200/s ETA done");
+    }
+
+    #[test]
+    fn test_format_line_without_total() {
+        let line = format_line("Iterations", 12, None, Duration::from_secs(4));
+        assert_eq!(line, "Iterations: 12 3/s");
+    }
+
+    #[test]
+    fn test_format_line_no_elapsed_time() {
+        let line = format_line("Scanning", 0, Some(10), Duration::ZERO);
+        assert_eq!(line, "Scanning: 0/10 (0.0%) 0/s ETA --");
+    }
+}
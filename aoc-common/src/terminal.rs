@@ -0,0 +1,342 @@
+use std::io::{IsTerminal, Write};
+use std::time::Duration;
+
+// Clear the screen and move the cursor to the top left
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Default,
+    Grey,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Colour {
+    // ANSI foreground code
+    fn code(&self) -> u8 {
+        match self {
+            Colour::Default => 39,
+            Colour::Grey => 90,
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+            Colour::White => 37,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: char,
+    pub colour: Colour,
+    // Drawn in reverse video so it stands out
+    pub highlighted: bool,
+}
+
+impl Cell {
+    pub fn new(symbol: char, colour: Colour) -> Self {
+        Cell {
+            symbol,
+            colour,
+            highlighted: false,
+        }
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell::new(' ', Colour::Default)
+    }
+}
+
+// A block of cells ready to be drawn to the terminal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize) -> Self {
+        Frame {
+            width,
+            height,
+            cells: vec![Cell::default(); width * height],
+        }
+    }
+
+    // Draw the part of a char grid inside the viewport, mapping each char to a cell
+    pub fn from_grid(
+        grid: &[Vec<char>],
+        viewport: &Viewport,
+        style: impl Fn(char) -> Cell,
+    ) -> Self {
+        let mut frame = Frame::new(viewport.width, viewport.height);
+        for (row, line) in grid
+            .iter()
+            .skip(viewport.y)
+            .take(viewport.height)
+            .enumerate()
+        {
+            for (col, &c) in line
+                .iter()
+                .skip(viewport.x)
+                .take(viewport.width)
+                .enumerate()
+            {
+                frame.set(col, row, style(c));
+            }
+        }
+        frame
+    }
+
+    pub fn get(&self, col: usize, row: usize) -> Option<&Cell> {
+        if col < self.width && row < self.height {
+            self.cells.get(row * self.width + col)
+        } else {
+            None
+        }
+    }
+
+    // Out of range cells are ignored so callers don't need to clip
+    pub fn set(&mut self, col: usize, row: usize, cell: Cell) {
+        if col < self.width && row < self.height {
+            self.cells[row * self.width + col] = cell;
+        }
+    }
+
+    pub fn highlight(&mut self, col: usize, row: usize) {
+        if col < self.width && row < self.height {
+            self.cells[row * self.width + col].highlighted = true;
+        }
+    }
+
+    // Highlight every cell between two corners, inclusive
+    pub fn highlight_rect(&mut self, corner1: (usize, usize), corner2: (usize, usize)) {
+        let (min_col, max_col) = (corner1.0.min(corner2.0), corner1.0.max(corner2.0));
+        let (min_row, max_row) = (corner1.1.min(corner2.1), corner1.1.max(corner2.1));
+        for row in min_row..=max_row.min(self.height.saturating_sub(1)) {
+            for col in min_col..=max_col.min(self.width.saturating_sub(1)) {
+                self.highlight(col, row);
+            }
+        }
+    }
+
+    // Straight line between two cells, only horizontal and vertical lines are needed so far
+    pub fn draw_line(&mut self, from: (usize, usize), to: (usize, usize), cell: Cell) {
+        if from.0 == to.0 {
+            for row in from.1.min(to.1)..=from.1.max(to.1) {
+                self.set(from.0, row, cell);
+            }
+        } else if from.1 == to.1 {
+            for col in from.0.min(to.0)..=from.0.max(to.0) {
+                self.set(col, from.1, cell);
+            }
+        } else {
+            // diagonal, just mark the ends
+            self.set(from.0, from.1, cell);
+            self.set(to.0, to.1, cell);
+        }
+    }
+
+    // Plain text, one line per row
+    pub fn to_plain(&self) -> String {
+        let mut out = String::new();
+        for row in self.cells.chunks(self.width.max(1)) {
+            out.extend(row.iter().map(|cell| cell.symbol));
+            out.push('\n');
+        }
+        out
+    }
+
+    // Text with ANSI colour codes, escape codes are only written when the style changes
+    pub fn to_ansi(&self) -> String {
+        let mut out = String::new();
+        for row in self.cells.chunks(self.width.max(1)) {
+            let mut current: Option<(Colour, bool)> = None;
+            for cell in row {
+                let style = (cell.colour, cell.highlighted);
+                if current != Some(style) {
+                    let reverse = if cell.highlighted { ";7" } else { "" };
+                    out.push_str(&format!("\x1b[0;{}{}m", cell.colour.code(), reverse));
+                    current = Some(style);
+                }
+                out.push(cell.symbol);
+            }
+            out.push_str(RESET);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+// Which part of a grid is drawn
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Viewport {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Viewport {
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Viewport {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+// Maps a large coordinate space onto a frame, shrinking both axes by the same factor
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scale {
+    pub min_x: u64,
+    pub min_y: u64,
+    pub factor: u64,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Scale {
+    // Smallest whole factor that fits the inclusive range into max_width x max_height
+    pub fn fit(min: (u64, u64), max: (u64, u64), max_width: usize, max_height: usize) -> Self {
+        let span_x = max.0 - min.0 + 1;
+        let span_y = max.1 - min.1 + 1;
+        let factor = span_x
+            .div_ceil(max_width.max(1) as u64)
+            .max(span_y.div_ceil(max_height.max(1) as u64))
+            .max(1);
+        Scale {
+            min_x: min.0,
+            min_y: min.1,
+            factor,
+            width: span_x.div_ceil(factor) as usize,
+            height: span_y.div_ceil(factor) as usize,
+        }
+    }
+
+    pub fn to_cell(&self, x: u64, y: u64) -> (usize, usize) {
+        (
+            ((x - self.min_x) / self.factor) as usize,
+            ((y - self.min_y) / self.factor) as usize,
+        )
+    }
+
+    pub fn frame(&self) -> Frame {
+        Frame::new(self.width, self.height)
+    }
+}
+
+// Colours only when stdout is a terminal, plain text when piped to a file
+pub fn print_frame(frame: &Frame) {
+    if std::io::stdout().is_terminal() {
+        print!("{}", frame.to_ansi());
+    } else {
+        print!("{}", frame.to_plain());
+    }
+}
+
+// Terminal size from COLUMNS and LINES, falling back to 80x24
+pub fn terminal_size() -> (usize, usize) {
+    let read = |name: &str, default: usize| {
+        std::env::var(name)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(default)
+    };
+    (read("COLUMNS", 80), read("LINES", 24))
+}
+
+// Draws frames one after another in place at a fixed speed
+pub struct Player {
+    pub delay: Duration,
+}
+
+impl Player {
+    pub fn new(delay: Duration) -> Self {
+        Player { delay }
+    }
+
+    // The caption is printed under each frame, e.g. the iteration number
+    pub fn play(
+        &self,
+        frames: impl Iterator<Item = (Frame, String)>,
+        out: &mut impl Write,
+    ) -> std::io::Result<()> {
+        for (frame, caption) in frames {
+            write!(out, "{}{}{}", CLEAR_SCREEN, frame.to_ansi(), caption)?;
+            writeln!(out)?;
+            out.flush()?;
+            std::thread::sleep(self.delay);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_grid_viewport() {
+        let grid: Vec<Vec<char>> = vec!["abc".chars().collect(), "def".chars().collect()];
+        let frame = Frame::from_grid(&grid, &Viewport::new(1, 1, 4, 2), |c| {
+            Cell::new(c, Colour::Default)
+        });
+        assert_eq!(frame.to_plain(), "ef  \n    \n");
+    }
+
+    #[test]
+    fn test_to_ansi() {
+        let mut frame = Frame::new(3, 1);
+        frame.set(0, 0, Cell::new('@', Colour::Green));
+        frame.set(1, 0, Cell::new('@', Colour::Green));
+        frame.set(2, 0, Cell::new('.', Colour::Grey));
+        frame.highlight(2, 0);
+        assert_eq!(frame.to_ansi(), "\x1b[0;32m@@\x1b[0;90;7m.\x1b[0m\n");
+    }
+
+    #[test]
+    fn test_highlight_rect_and_lines() {
+        let mut frame = Frame::new(4, 3);
+        frame.draw_line((0, 0), (3, 0), Cell::new('#', Colour::Red));
+        frame.draw_line((3, 2), (3, 0), Cell::new('#', Colour::Red));
+        frame.highlight_rect((2, 2), (1, 1));
+        frame.highlight_rect((3, 2), (9, 9));
+        assert_eq!(frame.to_plain(), "####\n   #\n   #\n");
+        assert!(frame.get(1, 1).unwrap().highlighted);
+        assert!(frame.get(2, 2).unwrap().highlighted);
+        assert!(frame.get(3, 2).unwrap().highlighted);
+        assert!(!frame.get(0, 0).unwrap().highlighted);
+        assert!(frame.get(4, 0).is_none());
+    }
+
+    #[test]
+    fn test_scale_fit() {
+        let scale = Scale::fit((0, 0), (99_999, 49_999), 80, 40);
+        assert_eq!(scale.factor, 1250);
+        assert_eq!((scale.width, scale.height), (80, 40));
+        assert_eq!(scale.to_cell(99_999, 0), (79, 0));
+        assert_eq!(scale.to_cell(1249, 1250), (0, 1));
+
+        // small spaces aren't scaled up
+        let scale = Scale::fit((2, 1), (11, 7), 80, 40);
+        assert_eq!(scale.factor, 1);
+        assert_eq!((scale.width, scale.height), (10, 7));
+        assert_eq!(scale.to_cell(2, 1), (0, 0));
+    }
+}
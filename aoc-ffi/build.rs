@@ -0,0 +1,54 @@
+#[path = "src/abi.rs"]
+mod abi;
+
+use std::fmt::Write;
+use std::path::Path;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/abi.rs");
+
+    let header = generate_header();
+    let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("include/aoc.h");
+
+    // only touch the file when it changes so C builds don't needlessly rebuild
+    let current = std::fs::read_to_string(&path).unwrap_or_default();
+    if current != header {
+        std::fs::create_dir_all(path.parent().unwrap()).expect("Failed to create include dir");
+        std::fs::write(&path, header).expect("Failed to write header");
+    }
+}
+
+fn generate_header() -> String {
+    let mut out = String::new();
+    out.push_str("/* Generated by aoc-ffi/build.rs from src/abi.rs, do not edit by hand. */\n");
+    out.push_str("#ifndef AOC_H\n#define AOC_H\n\n");
+    out.push_str("#include <stddef.h>\n#include <stdint.h>\n\n");
+    out.push_str("#ifdef __cplusplus\nextern \"C\" {\n#endif\n\n");
+
+    for (name, value, description) in abi::STATUS_CODES {
+        writeln!(out, "/* {} */\n#define {} {}", description, name, value).unwrap();
+    }
+
+    out.push_str(
+        "\n/*\n \
+         * Every solver reads input_len bytes of UTF-8 puzzle input and, unless it\n \
+         * returns AOC_NULL_ARGUMENT, writes a newly allocated NUL-terminated string\n \
+         * to *answer: the answer on AOC_OK, otherwise an error message.\n \
+         * The caller must release it with aoc_free_string.\n \
+         */\n",
+    );
+    for (day, description) in abi::DAYS {
+        writeln!(
+            out,
+            "\n/* {} */\nint32_t aoc_{}_solve(const uint8_t *input, size_t input_len, char **answer);",
+            description, day
+        )
+        .unwrap();
+    }
+
+    out.push_str("\n/* Free a string returned by one of the solvers. NULL is ignored. */\n");
+    out.push_str("void aoc_free_string(char *s);\n\n");
+    out.push_str("#ifdef __cplusplus\n}\n#endif\n\n#endif /* AOC_H */\n");
+    out
+}
@@ -0,0 +1,62 @@
+/* Generated by aoc-ffi/build.rs from src/abi.rs, do not edit by hand. */
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The answer was written to *answer */
+#define AOC_OK 0
+/* input or answer was NULL, nothing was written */
+#define AOC_NULL_ARGUMENT 1
+/* input was not valid UTF-8, *answer holds the error */
+#define AOC_INVALID_UTF8 2
+/* The solver rejected the input, *answer holds the error */
+#define AOC_SOLVER_FAILED 3
+
+/*
+ * Every solver reads input_len bytes of UTF-8 puzzle input and, unless it
+ * returns AOC_NULL_ARGUMENT, writes a newly allocated NUL-terminated string
+ * to *answer: the answer on AOC_OK, otherwise an error message.
+ * The caller must release it with aoc_free_string.
+ */
+
+/* Day 1: total zero events while turning the dial */
+int32_t aoc_day_1_solve(const uint8_t *input, size_t input_len, char **answer);
+
+/* Day 2: sum of the invalid ids in every range */
+int32_t aoc_day_2_solve(const uint8_t *input, size_t input_len, char **answer);
+
+/* Day 3: total max joltage across the battery banks */
+int32_t aoc_day_3_solve(const uint8_t *input, size_t input_len, char **answer);
+
+/* Day 4: items removed once the grid stops changing */
+int32_t aoc_day_4_solve(const uint8_t *input, size_t input_len, char **answer);
+
+/* Day 5: number of ids covered by the fresh ranges */
+int32_t aoc_day_5_solve(const uint8_t *input, size_t input_len, char **answer);
+
+/* Day 6 part 1: sum of the row-wise calculations */
+int32_t aoc_day_6_part_1_solve(const uint8_t *input, size_t input_len, char **answer);
+
+/* Day 6 part 2: sum of the column-wise calculations */
+int32_t aoc_day_6_part_2_solve(const uint8_t *input, size_t input_len, char **answer);
+
+/* Day 8: product of the three largest circuits */
+int32_t aoc_day_8_solve(const uint8_t *input, size_t input_len, char **answer);
+
+/* Day 9: area of the largest rectangle inside the shape */
+int32_t aoc_day_9_solve(const uint8_t *input, size_t input_len, char **answer);
+
+/* Free a string returned by one of the solvers. NULL is ignored. */
+void aoc_free_string(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* AOC_H */
@@ -0,0 +1,43 @@
+// Shared between the library and build.rs, which generates include/aoc.h from these tables
+
+// Status codes returned by every solver: (name, value, description)
+pub const STATUS_CODES: &[(&str, i32, &str)] = &[
+    ("AOC_OK", 0, "The answer was written to *answer"),
+    (
+        "AOC_NULL_ARGUMENT",
+        1,
+        "input or answer was NULL, nothing was written",
+    ),
+    (
+        "AOC_INVALID_UTF8",
+        2,
+        "input was not valid UTF-8, *answer holds the error",
+    ),
+    (
+        "AOC_SOLVER_FAILED",
+        3,
+        "The solver rejected the input, *answer holds the error",
+    ),
+];
+
+// Exported solvers: (day, description), each becomes aoc_<day>_solve
+pub const DAYS: &[(&str, &str)] = &[
+    ("day_1", "Day 1: total zero events while turning the dial"),
+    ("day_2", "Day 2: sum of the invalid ids in every range"),
+    ("day_3", "Day 3: total max joltage across the battery banks"),
+    ("day_4", "Day 4: items removed once the grid stops changing"),
+    ("day_5", "Day 5: number of ids covered by the fresh ranges"),
+    (
+        "day_6_part_1",
+        "Day 6 part 1: sum of the row-wise calculations",
+    ),
+    (
+        "day_6_part_2",
+        "Day 6 part 2: sum of the column-wise calculations",
+    ),
+    ("day_8", "Day 8: product of the three largest circuits"),
+    (
+        "day_9",
+        "Day 9: area of the largest rectangle inside the shape",
+    ),
+];
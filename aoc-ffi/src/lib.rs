@@ -0,0 +1,145 @@
+use std::any::Any;
+use std::ffi::{CString, c_char};
+use std::panic;
+
+pub mod abi;
+
+pub const AOC_OK: i32 = 0;
+pub const AOC_NULL_ARGUMENT: i32 = 1;
+pub const AOC_INVALID_UTF8: i32 = 2;
+pub const AOC_SOLVER_FAILED: i32 = 3;
+
+// Declare an exported solver for each day, see abi::DAYS for the header side
+macro_rules! export_solvers {
+    ($($name:ident => $solve:path,)*) => {
+        $(
+            /// # Safety
+            /// `input` must point to `input_len` readable bytes and `answer` must be
+            /// writable, the string written to it must be freed with `aoc_free_string`.
+            #[unsafe(no_mangle)]
+            pub unsafe extern "C" fn $name(
+                input: *const u8,
+                input_len: usize,
+                answer: *mut *mut c_char,
+            ) -> i32 {
+                unsafe { run_solver(input, input_len, answer, $solve) }
+            }
+        )*
+    };
+}
+
+export_solvers! {
+    aoc_day_1_solve => day_1::solve,
+    aoc_day_2_solve => day_2::solve,
+    aoc_day_3_solve => day_3::solve,
+    aoc_day_4_solve => day_4::solve,
+    aoc_day_5_solve => day_5::solve,
+    aoc_day_6_part_1_solve => day_6_part_1::solve,
+    aoc_day_6_part_2_solve => day_6_part_2::solve,
+    aoc_day_8_solve => day_8::solve,
+    aoc_day_9_solve => day_9::solve,
+}
+
+/// # Safety
+/// `s` must be NULL or a string returned by one of the solvers, and not freed already.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+// The solvers panic on bad input, so catch that and hand back the message instead
+unsafe fn run_solver<T: ToString>(
+    input: *const u8,
+    input_len: usize,
+    answer: *mut *mut c_char,
+    solve: fn(&str) -> T,
+) -> i32 {
+    if input.is_null() || answer.is_null() {
+        return AOC_NULL_ARGUMENT;
+    }
+
+    let bytes = unsafe { std::slice::from_raw_parts(input, input_len) };
+    let (status, text) = match std::str::from_utf8(bytes) {
+        Err(e) => (AOC_INVALID_UTF8, e.to_string()),
+        Ok(input) => match panic::catch_unwind(|| solve(input).to_string()) {
+            Ok(result) => (AOC_OK, result),
+            Err(payload) => (AOC_SOLVER_FAILED, panic_message(payload)),
+        },
+    };
+
+    unsafe { *answer = to_c_string(text) };
+    status
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Solver panicked".to_string()
+    }
+}
+
+fn to_c_string(text: String) -> *mut c_char {
+    // interior NULs would truncate the string on the C side anyway
+    let text = text.replace('\0', "");
+    CString::new(text).expect("NULs removed above").into_raw()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    fn call(
+        solver: unsafe extern "C" fn(*const u8, usize, *mut *mut c_char) -> i32,
+        input: &[u8],
+    ) -> (i32, String) {
+        let mut answer: *mut c_char = std::ptr::null_mut();
+        let status = unsafe { solver(input.as_ptr(), input.len(), &mut answer) };
+        let text = unsafe { CStr::from_ptr(answer) }
+            .to_string_lossy()
+            .into_owned();
+        unsafe { aoc_free_string(answer) };
+        (status, text)
+    }
+
+    #[test]
+    fn test_status_codes_match_abi() {
+        let codes: Vec<(&str, i32)> = abi::STATUS_CODES.iter().map(|c| (c.0, c.1)).collect();
+        assert_eq!(
+            codes,
+            vec![
+                ("AOC_OK", AOC_OK),
+                ("AOC_NULL_ARGUMENT", AOC_NULL_ARGUMENT),
+                ("AOC_INVALID_UTF8", AOC_INVALID_UTF8),
+                ("AOC_SOLVER_FAILED", AOC_SOLVER_FAILED),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_solver_ok() {
+        let (status, text) = call(aoc_day_5_solve, b"3-5\n10-14\n16-20\n12-18\n\n1\n5\n");
+        assert_eq!(status, AOC_OK);
+        assert_eq!(text, "14");
+    }
+
+    #[test]
+    fn test_solver_errors() {
+        let (status, _) = call(aoc_day_3_solve, &[0xff, 0xfe]);
+        assert_eq!(status, AOC_INVALID_UTF8);
+
+        let (status, text) = call(aoc_day_3_solve, b"12a4\n");
+        assert_eq!(status, AOC_SOLVER_FAILED);
+        assert_eq!(text, "Invalid digit");
+
+        let mut answer: *mut c_char = std::ptr::null_mut();
+        let status = unsafe { aoc_day_1_solve(std::ptr::null(), 0, &mut answer) };
+        assert_eq!(status, AOC_NULL_ARGUMENT);
+        assert!(answer.is_null());
+    }
+}
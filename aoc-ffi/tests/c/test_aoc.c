@@ -0,0 +1,65 @@
+/*
+ * Exercises the C ABI from plain C. Built and run by tests/c_abi.rs, or by hand:
+ *   cargo build
+ *   cc -I include tests/c/test_aoc.c target/debug/libaoc_ffi.a -lpthread -ldl -lm -o test_aoc
+ */
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+typedef int32_t (*solver_fn)(const uint8_t *, size_t, char **);
+
+static int failures = 0;
+
+static void check(const char *name, solver_fn solver, const char *input, int32_t expected_status,
+                  const char *expected_answer) {
+    char *answer = NULL;
+    int32_t status = solver((const uint8_t *)input, strlen(input), &answer);
+
+    if (status != expected_status || answer == NULL ||
+        (expected_answer != NULL && strcmp(answer, expected_answer) != 0)) {
+        printf("FAIL %s: status %d answer %s\n", name, status, answer ? answer : "(null)");
+        failures++;
+    } else {
+        printf("ok   %s: %s\n", name, answer);
+    }
+    aoc_free_string(answer);
+}
+
+int main(void) {
+    check("day 1", aoc_day_1_solve, "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n", AOC_OK, "9");
+    check("day 2", aoc_day_2_solve, "95-115,998-1012", AOC_OK, "2219");
+    check("day 3", aoc_day_3_solve,
+          "987654321111111\n811111111111119\n234234234234278\n818181911112111\n", AOC_OK,
+          "3121910778619");
+    check("day 4", aoc_day_4_solve,
+          "..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n"
+          ".@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.\n",
+          AOC_OK, "43");
+    check("day 5", aoc_day_5_solve, "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32\n", AOC_OK,
+          "14");
+    check("day 6 part 1", aoc_day_6_part_1_solve,
+          "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n", AOC_OK,
+          "4277556");
+    check("day 6 part 2", aoc_day_6_part_2_solve,
+          "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n", AOC_OK,
+          "3263827");
+    check("day 8", aoc_day_8_solve, "0,0,0\n1,0,0\n5,5,5\n6,5,5\n20,20,20\n", AOC_OK, "5");
+    check("day 9", aoc_day_9_solve, "7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3\n", AOC_OK, "24");
+
+    /* errors come back as a status plus a message the caller still frees */
+    check("bad digit", aoc_day_3_solve, "12a4\n", AOC_SOLVER_FAILED, "Invalid digit");
+    check("bad utf-8", aoc_day_3_solve, "\xff\xfe", AOC_INVALID_UTF8, NULL);
+
+    char *answer = NULL;
+    if (aoc_day_1_solve(NULL, 0, &answer) != AOC_NULL_ARGUMENT || answer != NULL) {
+        printf("FAIL null input\n");
+        failures++;
+    } else {
+        printf("ok   null input\n");
+    }
+
+    printf("%d failure(s)\n", failures);
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+// Build tests/c/test_aoc.c against the static library with the system cc and run it
+#[test]
+fn test_c_program() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // test binaries live in target/<profile>/deps, the libraries one level up
+    let profile_dir = std::env::current_exe()
+        .expect("Failed to find test binary")
+        .parent()
+        .and_then(|deps| deps.parent())
+        .expect("Failed to find target dir")
+        .to_path_buf();
+    let static_lib = profile_dir.join("libaoc_ffi.a");
+    let output = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("test_aoc");
+
+    let status = Command::new("cc")
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg(manifest_dir.join("tests/c/test_aoc.c"))
+        .arg(&static_lib)
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&output)
+        .status()
+        .expect("Failed to run cc");
+    assert!(status.success(), "cc failed to build the C test program");
+
+    let run = Command::new(&output)
+        .output()
+        .expect("Failed to run C test program");
+    let stdout = String::from_utf8_lossy(&run.stdout);
+    assert!(run.status.success(), "C test program failed:\n{}", stdout);
+}
@@ -0,0 +1,64 @@
+mod repl;
+
+use std::path::PathBuf;
+
+const USAGE: &str = "Usage: aoc repl <day> [--input <path>]";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("repl") => run_repl(&args[1..]),
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    }
+}
+
+fn run_repl(args: &[String]) {
+    let day = match args.first() {
+        Some(day) => day.clone(),
+        None => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let input_path = match args.get(1).map(String::as_str) {
+        Some("--input") => match args.get(2) {
+            Some(path) => PathBuf::from(path),
+            None => {
+                eprintln!("{}", USAGE);
+                std::process::exit(2);
+            }
+        },
+        Some(other) => {
+            eprintln!("Unknown option: {}\n{}", other, USAGE);
+            std::process::exit(2);
+        }
+        None => default_input_path(&day),
+    };
+
+    let input = std::fs::read_to_string(&input_path)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {}", input_path.display(), e));
+
+    let mut session = match repl::new_session(&day, &input) {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let stdin = std::io::stdin();
+    repl::run(session.as_mut(), stdin.lock(), std::io::stdout()).expect("Failed to run repl");
+}
+
+// Each day keeps its puzzle input next to its own Cargo.toml
+fn default_input_path(day: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("day-{}", day))
+        .join("input.txt")
+}
@@ -0,0 +1,108 @@
+mod day_1;
+mod day_4;
+mod day_5;
+mod day_8;
+mod day_9;
+
+use std::io::{BufRead, Write};
+
+const BUILTIN_HELP: &str =
+    "show  print the current state\nhelp  list the commands\nquit  leave the repl";
+
+// A day's parsed input that commands can be run against
+pub trait Session {
+    // Shown in the prompt, e.g. day-1
+    fn name(&self) -> &'static str;
+    // One line per supported command
+    fn help(&self) -> &'static str;
+    // Describe the current state
+    fn show(&self) -> String;
+    // Run a command, returning the output to print
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String>;
+}
+
+pub fn new_session(day: &str, input: &str) -> Result<Box<dyn Session>, String> {
+    match day {
+        "1" => Ok(Box::new(day_1::Day1Session::new(input))),
+        "4" => Ok(Box::new(day_4::Day4Session::new(input))),
+        "5" => Ok(Box::new(day_5::Day5Session::new(input))),
+        "8" => Ok(Box::new(day_8::Day8Session::new(input))),
+        "9" => Ok(Box::new(day_9::Day9Session::new(input))),
+        _ => Err(format!("No repl for day {}, try 1, 4, 5, 8 or 9", day)),
+    }
+}
+
+// Read commands line by line until `quit` or the end of input
+pub fn run(
+    session: &mut dyn Session,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> std::io::Result<()> {
+    writeln!(output, "{}", session.show())?;
+    write!(output, "{}> ", session.name())?;
+    output.flush()?;
+
+    for line in input.lines() {
+        let line = line?;
+        let mut words = line.split_whitespace();
+
+        if let Some(command) = words.next() {
+            let args: Vec<&str> = words.collect();
+            match command {
+                "quit" | "exit" => break,
+                "help" => writeln!(output, "{}\n{}", session.help(), BUILTIN_HELP)?,
+                "show" => writeln!(output, "{}", session.show())?,
+                _ => match session.execute(command, &args) {
+                    Ok(text) => writeln!(output, "{}", text)?,
+                    Err(e) => writeln!(output, "Error: {}", e)?,
+                },
+            }
+        }
+
+        write!(output, "{}> ", session.name())?;
+        output.flush()?;
+    }
+    writeln!(output)?;
+    Ok(())
+}
+
+// Parse the single argument most commands take
+fn parse_arg<T: std::str::FromStr>(args: &[&str], usage: &str) -> Result<T, String> {
+    match args {
+        [arg] => arg
+            .parse::<T>()
+            .map_err(|_| format!("Invalid argument {}, usage: {}", arg, usage)),
+        _ => Err(format!("usage: {}", usage)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let mut session = new_session("1", "L68\n").unwrap();
+        let input = "rotate L50\nbogus\nquit\nrotate R1\n";
+        let mut output: Vec<u8> = Vec::new();
+        run(session.as_mut(), input.as_bytes(), &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("position 0"));
+        assert!(output.contains("Error: Unknown command bogus"));
+        // nothing after quit runs
+        assert!(!output.contains("position 1,"));
+    }
+
+    #[test]
+    fn test_unknown_day() {
+        assert!(new_session("7", "").is_err());
+    }
+
+    #[test]
+    fn test_parse_arg() {
+        assert_eq!(parse_arg::<u64>(&["17"], "query <id>"), Ok(17));
+        assert!(parse_arg::<u64>(&["x"], "query <id>").is_err());
+        assert!(parse_arg::<u64>(&[], "query <id>").is_err());
+    }
+}
@@ -0,0 +1,121 @@
+use day_1::{Lock, Rotation, parse_rotation, parse_rotations};
+
+use super::{Session, parse_arg};
+
+pub struct Day1Session {
+    lock: Lock,
+    rotations: Vec<Rotation>,
+    // How many of the input rotations have been applied with `next`
+    next_rotation: usize,
+}
+
+impl Day1Session {
+    pub fn new(input: &str) -> Self {
+        let mut lock = Lock::new();
+        lock.enable_history();
+        Day1Session {
+            lock,
+            rotations: parse_rotations(input),
+            next_rotation: 0,
+        }
+    }
+}
+
+impl Session for Day1Session {
+    fn name(&self) -> &'static str {
+        "day-1"
+    }
+
+    fn help(&self) -> &'static str {
+        "rotate <L|R><clicks>  turn the dial, e.g. rotate L68\n\
+         next [n]              apply the next n rotations from the input\n\
+         undo                  take back the last rotation\n\
+         redo                  apply the last undone rotation again\n\
+         seek <n>              go to the state after the first n rotations\n\
+         reset                 back to a new lock at the start of the input"
+    }
+
+    fn show(&self) -> String {
+        format!(
+            "position {}, zero_passed_counter {}, zero_finished_counter {}, input rotation {}/{}",
+            self.lock.dial_position,
+            self.lock.zero_passed_counter,
+            self.lock.zero_finished_counter,
+            self.next_rotation,
+            self.rotations.len()
+        )
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "rotate" => {
+                let rotation: String = parse_arg(args, "rotate <L|R><clicks>")?;
+                self.lock.rotate(&parse_rotation(&rotation)?);
+            }
+            "next" => {
+                let count: usize = if args.is_empty() {
+                    1
+                } else {
+                    parse_arg(args, "next [n]")?
+                };
+                let end = (self.next_rotation + count).min(self.rotations.len());
+                for rotation in &self.rotations[self.next_rotation..end] {
+                    self.lock.rotate(rotation);
+                }
+                self.next_rotation = end;
+            }
+            "undo" => {
+                if !self.lock.undo() {
+                    return Err("Nothing to undo".to_string());
+                }
+            }
+            "redo" => {
+                if !self.lock.redo() {
+                    return Err("Nothing to redo".to_string());
+                }
+            }
+            "seek" => {
+                let step: usize = parse_arg(args, "seek <n>")?;
+                self.lock.seek(step)?;
+            }
+            "reset" => {
+                self.lock = Lock::with_dial(self.lock.dial);
+                self.lock.enable_history();
+                self.next_rotation = 0;
+            }
+            _ => return Err(format!("Unknown command {}", command)),
+        }
+        Ok(self.show())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate_and_next() {
+        let mut session = Day1Session::new("L68\nL30\n");
+        assert_eq!(
+            session.execute("rotate", &["L50"]).unwrap(),
+            "position 0, zero_passed_counter 1, zero_finished_counter 1, input rotation 0/2"
+        );
+        assert_eq!(
+            session.execute("next", &["5"]).unwrap(),
+            "position 2, zero_passed_counter 1, zero_finished_counter 1, input rotation 2/2"
+        );
+        assert!(session.execute("rotate", &["X1"]).is_err());
+
+        let undone = session.execute("undo", &[]).unwrap();
+        assert!(undone.starts_with("position 32,"));
+        let start = session.execute("seek", &["0"]).unwrap();
+        assert!(start.starts_with("position 50,"));
+        let redone = session.execute("redo", &[]).unwrap();
+        assert!(redone.starts_with("position 0,"));
+        assert!(session.execute("seek", &["9"]).is_err());
+
+        session.execute("reset", &[]).unwrap();
+        assert!(session.execute("undo", &[]).is_err());
+        assert!(session.show().starts_with("position 50,"));
+    }
+}
@@ -0,0 +1,85 @@
+use day_4::{parse_grid, perform_processing};
+
+use super::{Session, parse_arg};
+
+pub struct Day4Session {
+    grid: Vec<Vec<char>>,
+    iterations: usize,
+    total_removed: usize,
+}
+
+impl Day4Session {
+    pub fn new(input: &str) -> Self {
+        Day4Session {
+            grid: parse_grid(input),
+            iterations: 0,
+            total_removed: 0,
+        }
+    }
+}
+
+impl Session for Day4Session {
+    fn name(&self) -> &'static str {
+        "day-4"
+    }
+
+    fn help(&self) -> &'static str {
+        "step [n]  run n removal iterations (default 1)"
+    }
+
+    fn show(&self) -> String {
+        let mut out = format!(
+            "after {} iterations, {} items removed",
+            self.iterations, self.total_removed
+        );
+        for row in &self.grid {
+            out.push('\n');
+            out.extend(row.iter());
+        }
+        out
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "step" => {
+                let count: usize = if args.is_empty() {
+                    1
+                } else {
+                    parse_arg(args, "step [n]")?
+                };
+                for _ in 0..count {
+                    let processed = perform_processing(self.grid.clone()).replace_xs_with_dots();
+                    if processed.items_removed == 0 {
+                        break;
+                    }
+                    self.grid = processed.output;
+                    self.iterations += 1;
+                    self.total_removed += processed.items_removed;
+                }
+                Ok(self.show())
+            }
+            _ => Err(format!("Unknown command {}", command)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step() {
+        let mut session = Day4Session::new("@@@\n@@@\n@@@\n");
+        assert_eq!(
+            session.execute("step", &[]).unwrap(),
+            "after 1 iterations, 4 items removed\n.@.\n@@@\n.@."
+        );
+        // stops once nothing more can be removed
+        session.execute("step", &["10"]).unwrap();
+        assert!(
+            session
+                .show()
+                .starts_with("after 3 iterations, 9 items removed")
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use day_5::{IdRange, merge_ranges, parse_input};
+
+use super::{Session, parse_arg};
+
+pub struct Day5Session {
+    ranges: Vec<IdRange>,
+    merged: Vec<IdRange>,
+    ids: Vec<u64>,
+}
+
+impl Day5Session {
+    pub fn new(input: &str) -> Self {
+        let (ranges, ids) = parse_input(input);
+        let merged = merge_ranges(&mut ranges.clone());
+        Day5Session {
+            ranges,
+            merged,
+            ids,
+        }
+    }
+}
+
+fn format_ranges<'a>(ranges: impl Iterator<Item = &'a IdRange>) -> String {
+    ranges
+        .map(|range| format!("{}-{}", range.start, range.end))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+impl Session for Day5Session {
+    fn name(&self) -> &'static str {
+        "day-5"
+    }
+
+    fn help(&self) -> &'static str {
+        "query <id>  list the input ranges containing id"
+    }
+
+    fn show(&self) -> String {
+        format!(
+            "{} ranges ({} after merging), {} ids",
+            self.ranges.len(),
+            self.merged.len(),
+            self.ids.len()
+        )
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "query" => {
+                let id: u64 = parse_arg(args, "query <id>")?;
+                let matches: Vec<&IdRange> = self
+                    .ranges
+                    .iter()
+                    .filter(|range| range.is_in_range(id))
+                    .collect();
+                if matches.is_empty() {
+                    Ok(format!("{} is not in any range", id))
+                } else {
+                    Ok(format!(
+                        "{} is in {} range(s): {}",
+                        id,
+                        matches.len(),
+                        format_ranges(matches.into_iter())
+                    ))
+                }
+            }
+            _ => Err(format!("Unknown command {}", command)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query() {
+        let mut session = Day5Session::new("3-5\n10-14\n16-20\n12-18\n\n1\n5\n");
+        assert_eq!(session.show(), "4 ranges (2 after merging), 2 ids");
+        assert_eq!(
+            session.execute("query", &["17"]).unwrap(),
+            "17 is in 2 range(s): 16-20, 12-18"
+        );
+        assert_eq!(
+            session.execute("query", &["8"]).unwrap(),
+            "8 is not in any range"
+        );
+        assert!(session.execute("query", &["-1"]).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+use day_8::{
+    Distance, build_circuits, circuit_sizes, closest_pairs, generate_distances, parse_boxes,
+};
+
+use aoc_common::progress::NoProgress;
+
+use super::{Session, parse_arg};
+
+pub struct Day8Session {
+    box_count: usize,
+    // Every pair, closest first
+    distances: Vec<Distance>,
+    connected: usize,
+}
+
+impl Day8Session {
+    pub fn new(input: &str) -> Self {
+        let boxes = parse_boxes(input);
+        let distances = generate_distances(&boxes, &mut NoProgress);
+        let pair_count = distances.len();
+        Day8Session {
+            box_count: boxes.len(),
+            distances: closest_pairs(distances, pair_count),
+            connected: 0,
+        }
+    }
+}
+
+impl Session for Day8Session {
+    fn name(&self) -> &'static str {
+        "day-8"
+    }
+
+    fn help(&self) -> &'static str {
+        "connect <n>  join the next n closest pairs\n\
+         reset        disconnect everything"
+    }
+
+    fn show(&self) -> String {
+        let circuits = build_circuits(&self.distances[..self.connected]);
+        let sizes = circuit_sizes(&circuits);
+        let product: u128 = sizes.iter().take(3).product();
+        format!(
+            "{} boxes, {}/{} pairs connected, {} circuits, largest {:?}, product of top three {}",
+            self.box_count,
+            self.connected,
+            self.distances.len(),
+            circuits.len(),
+            &sizes[..sizes.len().min(5)],
+            product
+        )
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "connect" => {
+                let count: usize = parse_arg(args, "connect <n>")?;
+                self.connected = (self.connected + count).min(self.distances.len());
+            }
+            "reset" => self.connected = 0,
+            _ => return Err(format!("Unknown command {}", command)),
+        }
+        Ok(self.show())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connect() {
+        let mut session = Day8Session::new("0,0,0\n1,0,0\n5,5,5\n6,5,5\n20,20,20\n");
+        assert_eq!(
+            session.execute("connect", &["2"]).unwrap(),
+            "5 boxes, 2/10 pairs connected, 2 circuits, largest [2, 2], product of top three 4"
+        );
+        assert!(
+            session
+                .execute("connect", &["100"])
+                .unwrap()
+                .starts_with("5 boxes, 10/10 pairs connected, 1 circuits")
+        );
+    }
+}
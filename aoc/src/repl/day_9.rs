@@ -0,0 +1,114 @@
+use std::collections::HashSet;
+
+use day_9::{Bounds, Position, Rectangle, check_rectangle_is_inside, fill_in_tiles, parse_tiles};
+
+use super::Session;
+
+pub struct Day9Session {
+    tile_count: usize,
+    bounds: Bounds,
+    outer_set: HashSet<Position>,
+    // Points already checked by earlier tests
+    valid_set: HashSet<Position>,
+    invalid_set: HashSet<Position>,
+}
+
+impl Day9Session {
+    pub fn new(input: &str) -> Self {
+        let (tiles, bounds) = parse_tiles(input);
+        let outer_set = fill_in_tiles(&tiles);
+        Day9Session {
+            tile_count: tiles.len(),
+            bounds,
+            valid_set: outer_set.clone(),
+            outer_set,
+            invalid_set: HashSet::new(),
+        }
+    }
+}
+
+fn parse_position(text: &str) -> Result<Position, String> {
+    let invalid = || format!("Invalid position {}, expected x,y", text);
+    let (x, y) = text.split_once(',').ok_or_else(invalid)?;
+    Ok(Position {
+        x: x.parse().map_err(|_| invalid())?,
+        y: y.parse().map_err(|_| invalid())?,
+    })
+}
+
+impl Session for Day9Session {
+    fn name(&self) -> &'static str {
+        "day-9"
+    }
+
+    fn help(&self) -> &'static str {
+        "test <x,y> <x,y>  check whether the rectangle between two corners is inside the shape"
+    }
+
+    fn show(&self) -> String {
+        format!(
+            "{} red tiles, {} boundary tiles, x {}-{}, y {}-{}, {} valid and {} invalid points cached",
+            self.tile_count,
+            self.outer_set.len(),
+            self.bounds.min_x.unwrap_or(0),
+            self.bounds.max_x.unwrap_or(0),
+            self.bounds.min_y.unwrap_or(0),
+            self.bounds.max_y.unwrap_or(0),
+            self.valid_set.len(),
+            self.invalid_set.len()
+        )
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "test" => {
+                let [first, second] = args else {
+                    return Err("usage: test <x,y> <x,y>".to_string());
+                };
+                let position1 = parse_position(first)?;
+                let position2 = parse_position(second)?;
+                let rectangle = Rectangle {
+                    area: position1.find_area(&position2),
+                    position1,
+                    position2,
+                };
+                let inside = check_rectangle_is_inside(
+                    &rectangle,
+                    &self.bounds,
+                    &self.outer_set,
+                    &mut self.valid_set,
+                    &mut self.invalid_set,
+                );
+                Ok(format!(
+                    "rectangle {},{} {},{} area {} is {}\n{}",
+                    rectangle.position1.x,
+                    rectangle.position1.y,
+                    rectangle.position2.x,
+                    rectangle.position2.y,
+                    rectangle.area,
+                    if inside { "inside" } else { "outside" },
+                    self.show()
+                ))
+            }
+            _ => Err(format!("Unknown command {}", command)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rectangle() {
+        let mut session = Day9Session::new("7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3\n");
+        let output = session.execute("test", &["2,3", "9,7"]).unwrap();
+        assert!(output.starts_with("rectangle 2,3 9,7 area 40 is outside"));
+
+        let output = session.execute("test", &["9,5", "2,3"]).unwrap();
+        assert!(output.starts_with("rectangle 9,5 2,3 area 24 is inside"));
+
+        assert!(session.execute("test", &["2,3"]).is_err());
+        assert!(session.execute("test", &["2;3", "9,7"]).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+use crate::{Direction, Lock, Rotation};
+
+// Everything that changes as the dial turns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockState {
+    pub dial_position: u32,
+    pub zero_passed_counter: u64,
+    pub zero_finished_counter: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub rotation: Rotation,
+    // State after the rotation
+    pub state: LockState,
+    // Zero events caused by this rotation alone
+    pub zero_passed: u64,
+    pub landed_on_zero: bool,
+}
+
+// The rotations applied to a lock, entries after `cursor` have been undone and can be redone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History {
+    pub initial: LockState,
+    pub entries: Vec<HistoryEntry>,
+    cursor: usize,
+}
+
+impl History {
+    pub fn new(initial: LockState) -> Self {
+        History {
+            initial,
+            entries: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    // Number of rotations currently applied
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    // A new rotation drops anything that was undone
+    pub fn record(&mut self, rotation: Rotation, state: LockState) {
+        let before = self.state_at(self.cursor);
+        self.entries.truncate(self.cursor);
+        self.entries.push(HistoryEntry {
+            rotation,
+            state,
+            zero_passed: state.zero_passed_counter - before.zero_passed_counter,
+            landed_on_zero: state.zero_finished_counter > before.zero_finished_counter,
+        });
+        self.cursor += 1;
+    }
+
+    // State after the first `step` rotations, 0 being the initial state
+    fn state_at(&self, step: usize) -> LockState {
+        match step {
+            0 => self.initial,
+            _ => self.entries[step - 1].state,
+        }
+    }
+
+    // One line per applied rotation with the resulting position, for plotting
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "step,direction,clicks,position,zero_passed,landed_on_zero,zero_passed_counter,zero_finished_counter\n",
+        );
+        for (i, entry) in self.entries[..self.cursor].iter().enumerate() {
+            let direction = match entry.rotation.direction {
+                Direction::Left => "L",
+                Direction::Right => "R",
+            };
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{}\n",
+                i + 1,
+                direction,
+                entry.rotation.clicks,
+                entry.state.dial_position,
+                entry.zero_passed,
+                entry.landed_on_zero,
+                entry.state.zero_passed_counter,
+                entry.state.zero_finished_counter
+            ));
+        }
+        csv
+    }
+}
+
+impl Lock {
+    pub fn state(&self) -> LockState {
+        LockState {
+            dial_position: self.dial_position,
+            zero_passed_counter: self.zero_passed_counter,
+            zero_finished_counter: self.zero_finished_counter,
+        }
+    }
+
+    fn set_state(&mut self, state: LockState) {
+        self.dial_position = state.dial_position;
+        self.zero_passed_counter = state.zero_passed_counter;
+        self.zero_finished_counter = state.zero_finished_counter;
+    }
+
+    // Start recording from the current state, any previous history is dropped
+    pub fn enable_history(&mut self) {
+        self.history = Some(History::new(self.state()));
+    }
+
+    // Go to the state after the first `step` recorded rotations
+    pub fn seek(&mut self, step: usize) -> Result<(), String> {
+        let history = self
+            .history
+            .as_mut()
+            .ok_or_else(|| "History is not enabled".to_string())?;
+        if step > history.entries.len() {
+            return Err(format!(
+                "Step {} is past the end of the history ({} rotations)",
+                step,
+                history.entries.len()
+            ));
+        }
+        history.cursor = step;
+        let state = history.state_at(step);
+        self.set_state(state);
+        Ok(())
+    }
+
+    // Returns false when there is nothing to undo
+    pub fn undo(&mut self) -> bool {
+        match self.history.as_ref().map(|history| history.cursor) {
+            Some(cursor) if cursor > 0 => self.seek(cursor - 1).is_ok(),
+            _ => false,
+        }
+    }
+
+    // Returns false when there is nothing to redo
+    pub fn redo(&mut self) -> bool {
+        match self.history.as_ref().map(|h| (h.cursor, h.entries.len())) {
+            Some((cursor, len)) if cursor < len => self.seek(cursor + 1).is_ok(),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_rotations;
+
+    fn lock_with_history(input: &str) -> Lock {
+        let mut lock = Lock::new();
+        lock.enable_history();
+        for rotation in parse_rotations(input) {
+            lock.rotate(&rotation);
+        }
+        lock
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut lock = lock_with_history("L50\nR250\nL1\n");
+        assert_eq!(lock.dial_position, 49);
+
+        assert!(lock.undo());
+        assert_eq!(lock.dial_position, 50);
+        assert_eq!(
+            (lock.zero_passed_counter, lock.zero_finished_counter),
+            (3, 1)
+        );
+        assert!(lock.undo());
+        assert!(lock.undo());
+        assert_eq!(lock.state(), lock.history.as_ref().unwrap().initial);
+        assert!(!lock.undo());
+
+        assert!(lock.redo());
+        assert_eq!(lock.dial_position, 0);
+        assert_eq!(
+            (lock.zero_passed_counter, lock.zero_finished_counter),
+            (1, 1)
+        );
+
+        // a new rotation replaces the ones that were undone
+        lock.rotate(&Rotation {
+            direction: Direction::Right,
+            clicks: 5,
+        });
+        assert!(!lock.redo());
+        assert_eq!(lock.history.as_ref().unwrap().entries.len(), 2);
+    }
+
+    #[test]
+    fn test_seek() {
+        let mut lock = lock_with_history("L50\nR250\nL1\n");
+        lock.seek(1).unwrap();
+        assert_eq!(lock.dial_position, 0);
+        lock.seek(3).unwrap();
+        assert_eq!(lock.dial_position, 49);
+        assert!(lock.seek(4).is_err());
+
+        let mut lock = Lock::new();
+        assert!(lock.seek(0).is_err());
+        assert!(!lock.undo());
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let mut lock = lock_with_history("L50\nR250\nL1\n");
+        lock.undo();
+        assert_eq!(
+            lock.history.as_ref().unwrap().to_csv(),
+            "step,direction,clicks,position,zero_passed,landed_on_zero,zero_passed_counter,zero_finished_counter\n\
+             1,L,50,0,1,true,1,1\n\
+             2,R,250,50,2,false,3,1\n"
+        );
+    }
+}
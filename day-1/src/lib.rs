@@ -0,0 +1,1098 @@
+pub mod history;
+pub mod multi;
+pub mod normalize;
+pub mod stats;
+
+use history::History;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rotation {
+    // Direction to rotate
+    pub direction: Direction,
+    // Number of clicks to rotate
+    pub clicks: u32,
+}
+
+impl std::fmt::Display for Rotation {
+    // Same L68 / R30 format parse_rotation reads
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let letter = match self.direction {
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+        };
+        write!(f, "{}{}", letter, self.clicks)
+    }
+}
+
+// Number of positions and where the dial starts, the puzzle uses 100 starting at 50
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DialSpec {
+    pub positions: u32,
+    pub start: u32,
+}
+
+impl DialSpec {
+    pub fn new(positions: u32, start: u32) -> Result<Self, String> {
+        if positions == 0 {
+            return Err("Dial must have at least one position".to_string());
+        }
+        if start >= positions {
+            return Err(format!(
+                "Start position {} is not on a dial of {} positions",
+                start, positions
+            ));
+        }
+        Ok(DialSpec { positions, start })
+    }
+
+    // Read `positions` and `start` from key = value lines, missing keys keep the default
+    pub fn from_config(text: &str) -> Result<Self, String> {
+        let default = DialSpec::default();
+        let mut positions = default.positions;
+        let mut start = default.start;
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("Expected key = value, got {}", line))?;
+            let value: u32 = value
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid value for {}: {}", key.trim(), value.trim()))?;
+            match key.trim() {
+                "positions" => positions = value,
+                "start" => start = value,
+                key => return Err(format!("Unknown dial setting {}", key)),
+            }
+        }
+
+        DialSpec::new(positions, start)
+    }
+
+    // Where the dial ends up after turning from `start`
+    fn position_after(&self, start: u32, rotation: &Rotation) -> u32 {
+        let size = self.positions as u64;
+        let clicks = rotation.clicks as u64 % size;
+        let position = match rotation.direction {
+            Direction::Left => (start as u64 + size - clicks) % size,
+            Direction::Right => (start as u64 + clicks) % size,
+        };
+        position as u32
+    }
+
+    // How many clicks land the dial on `target` while turning from `start`
+    fn count_arrivals(&self, start: u32, target: u32, rotation: &Rotation) -> u64 {
+        let size = self.positions as u64;
+        let (start, target) = (start as u64, target as u64);
+        // clicks until the first arrival, a full turn if we start on the target
+        let first = match rotation.direction {
+            Direction::Left => (start + size - target) % size,
+            Direction::Right => (target + size - start) % size,
+        };
+        let first = if first == 0 { size } else { first };
+
+        let clicks = rotation.clicks as u64;
+        if clicks < first {
+            0
+        } else {
+            1 + (clicks - first) / size
+        }
+    }
+}
+
+impl Default for DialSpec {
+    fn default() -> Self {
+        DialSpec {
+            positions: 100,
+            start: 50,
+        }
+    }
+}
+
+// What happened during a rotation, in the order it happened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockEvent {
+    RotationStarted {
+        position: u32,
+        direction: Direction,
+        clicks: u32,
+    },
+    // The dial reached `position` this many times, including the final click
+    Passed {
+        position: u32,
+        times: u32,
+    },
+    // Whole turns of the dial completed during the rotation
+    FullRevolution {
+        count: u32,
+    },
+    Landed {
+        position: u32,
+    },
+}
+
+// Receives events from Lock::rotate_with
+pub trait LockObserver {
+    fn on_event(&mut self, event: &LockEvent);
+}
+
+// For callers that only want the counters
+pub struct NoObserver;
+
+impl LockObserver for NoObserver {
+    fn on_event(&mut self, _event: &LockEvent) {}
+}
+
+// Keep every event, mostly useful in tests
+impl LockObserver for Vec<LockEvent> {
+    fn on_event(&mut self, event: &LockEvent) {
+        self.push(event.clone());
+    }
+}
+
+// Pass and landing counts for one watched position, counted the same way as zero
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Watch {
+    pub position: u32,
+    pub passed: u64,
+    pub landed: u64,
+}
+
+// Positions to count alongside zero, see Lock::rotate_watching
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchList {
+    pub watches: Vec<Watch>,
+}
+
+impl WatchList {
+    // Duplicates are dropped, every position has to be on the dial
+    pub fn new(positions: &[u32], dial: &DialSpec) -> Result<Self, String> {
+        let mut watches: Vec<Watch> = Vec::new();
+        for &position in positions {
+            if position >= dial.positions {
+                return Err(format!(
+                    "Watched position {} is not on a dial of {} positions",
+                    position, dial.positions
+                ));
+            }
+            if !watches.iter().any(|watch| watch.position == position) {
+                watches.push(Watch {
+                    position,
+                    passed: 0,
+                    landed: 0,
+                });
+            }
+        }
+        Ok(WatchList { watches })
+    }
+
+    // Comma separated positions, e.g. 0,25,50,75
+    pub fn parse(text: &str, dial: &DialSpec) -> Result<Self, String> {
+        let positions = text
+            .split(',')
+            .map(|part| {
+                part.trim()
+                    .parse::<u32>()
+                    .map_err(|_| format!("Invalid watched position {}", part.trim()))
+            })
+            .collect::<Result<Vec<u32>, String>>()?;
+        WatchList::new(&positions, dial)
+    }
+
+    pub fn get(&self, position: u32) -> Option<&Watch> {
+        self.watches.iter().find(|watch| watch.position == position)
+    }
+
+    // One line per watched position
+    pub fn report(&self) -> String {
+        self.watches
+            .iter()
+            .map(|watch| {
+                format!(
+                    "position {}: passed {}, landed {}",
+                    watch.position, watch.passed, watch.landed
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[derive(Debug)]
+pub struct Lock {
+    pub dial: DialSpec,
+    // Current position of the lock (0 to positions - 1)
+    pub dial_position: u32,
+    // zero-counter (how many times we've passed 0)
+    pub zero_passed_counter: u64,
+    // Number of times the dial has landed on 0
+    pub zero_finished_counter: u64,
+    // Every rotation so far once enable_history is called
+    pub history: Option<History>,
+}
+
+impl Lock {
+    pub fn new() -> Self {
+        Lock::with_dial(DialSpec::default())
+    }
+
+    pub fn with_dial(dial: DialSpec) -> Self {
+        Lock {
+            dial,
+            dial_position: dial.start,
+            zero_passed_counter: 0,
+            zero_finished_counter: 0,
+            history: None,
+        }
+    }
+
+    // Rotate the lock one click to the right
+    fn rotate_one_right(&mut self) {
+        self.dial_position = (self.dial_position + 1) % self.dial.positions;
+        if self.dial_position == 0 {
+            // Increment zero_passed_counter when passing 0
+            self.zero_passed_counter += 1;
+        }
+    }
+    // Rotate the lock one click to the left
+    fn rotate_one_left(&mut self) {
+        self.dial_position = (self.dial_position + self.dial.positions - 1) % self.dial.positions;
+        if self.dial_position == 0 {
+            // Increment zero_passed_counter when passing 0
+            self.zero_passed_counter += 1;
+        }
+    }
+
+    // Constant time regardless of the number of clicks, matches rotate_stepwise exactly
+    pub fn rotate(&mut self, rotation: &Rotation) {
+        let start = self.dial_position;
+        self.zero_passed_counter += self.dial.count_arrivals(start, 0, rotation);
+        self.dial_position = self.dial.position_after(start, rotation);
+
+        if self.dial_position == 0 {
+            self.zero_finished_counter += 1;
+        }
+
+        let state = self.state();
+        if let Some(history) = self.history.as_mut() {
+            history.record(*rotation, state);
+        }
+    }
+
+    // Same as rotate, also counting every watched position in constant time per position
+    pub fn rotate_watching(&mut self, rotation: &Rotation, watch_list: &mut WatchList) {
+        let start = self.dial_position;
+        self.rotate(rotation);
+        for watch in watch_list.watches.iter_mut() {
+            watch.passed += self.dial.count_arrivals(start, watch.position, rotation);
+            if watch.position == self.dial_position {
+                watch.landed += 1;
+            }
+        }
+    }
+
+    // Same as rotate, reporting each event to the observer
+    // Passed events come in the order each position is first reached, at most one per position
+    pub fn rotate_with(&mut self, rotation: &Rotation, observer: &mut dyn LockObserver) {
+        let start = self.dial_position;
+        let size = self.dial.positions;
+        observer.on_event(&LockEvent::RotationStarted {
+            position: start,
+            direction: rotation.direction,
+            clicks: rotation.clicks,
+        });
+
+        for k in 1..=rotation.clicks.min(size) {
+            let step = Rotation {
+                direction: rotation.direction,
+                clicks: k,
+            };
+            observer.on_event(&LockEvent::Passed {
+                position: self.dial.position_after(start, &step),
+                times: 1 + (rotation.clicks - k) / size,
+            });
+        }
+
+        let revolutions = rotation.clicks / size;
+        if revolutions > 0 {
+            observer.on_event(&LockEvent::FullRevolution { count: revolutions });
+        }
+
+        self.rotate(rotation);
+        observer.on_event(&LockEvent::Landed {
+            position: self.dial_position,
+        });
+    }
+
+    // Reference implementation, one click at a time
+    pub fn rotate_stepwise(&mut self, rotation: &Rotation) {
+        match rotation.direction {
+            Direction::Left => {
+                for _ in 0..rotation.clicks {
+                    self.rotate_one_left();
+                }
+            }
+            Direction::Right => {
+                for _ in 0..rotation.clicks {
+                    self.rotate_one_right();
+                }
+            }
+        }
+        if self.dial_position == 0 {
+            self.zero_finished_counter += 1;
+        }
+    }
+}
+
+// Rules for find_combination
+#[derive(Debug, Clone, Default)]
+pub struct SearchConstraints {
+    // Each rotation turns the other way to the one before
+    pub alternate: bool,
+    // Fewest clicks allowed in a single rotation
+    pub min_clicks: u32,
+}
+
+// Fewest clicks turning `direction` from `start` that stop on `target`
+fn clicks_to_reach(
+    dial: &DialSpec,
+    start: u32,
+    target: u32,
+    direction: Direction,
+    min_clicks: u32,
+) -> u64 {
+    let size = dial.positions as u64;
+    let (start, target) = (start as u64, target as u64);
+    let clicks = match direction {
+        Direction::Left => (start + size - target) % size,
+        Direction::Right => (target + size - start) % size,
+    };
+    let min_clicks = min_clicks as u64;
+    if clicks >= min_clicks {
+        clicks
+    } else {
+        // add whole turns until the minimum is met
+        clicks + (min_clicks - clicks).div_ceil(size) * size
+    }
+}
+
+// Rotations with the fewest total clicks that stop on each target in turn
+// Each stop is fixed by the target so only the directions need choosing, which are free
+// unless they must alternate. Ties go to Left.
+pub fn find_combination(
+    dial: &DialSpec,
+    start: u32,
+    targets: &[u32],
+    constraints: &SearchConstraints,
+) -> Result<Vec<Rotation>, String> {
+    for &position in std::iter::once(&start).chain(targets) {
+        if position >= dial.positions {
+            return Err(format!(
+                "Position {} is not on a dial of {} positions",
+                position, dial.positions
+            ));
+        }
+    }
+
+    // (total clicks, rotations) for sequences so far
+    let plan = |first: Option<Direction>| -> Result<(u64, Vec<Rotation>), String> {
+        let mut position = start;
+        let mut total = 0;
+        let mut rotations = Vec::new();
+        let mut direction = first;
+        for &target in targets {
+            let (chosen, clicks) = match direction {
+                Some(direction) => (
+                    direction,
+                    clicks_to_reach(dial, position, target, direction, constraints.min_clicks),
+                ),
+                None => {
+                    let left = clicks_to_reach(
+                        dial,
+                        position,
+                        target,
+                        Direction::Left,
+                        constraints.min_clicks,
+                    );
+                    let right = clicks_to_reach(
+                        dial,
+                        position,
+                        target,
+                        Direction::Right,
+                        constraints.min_clicks,
+                    );
+                    if left <= right {
+                        (Direction::Left, left)
+                    } else {
+                        (Direction::Right, right)
+                    }
+                }
+            };
+            let clicks = u32::try_from(clicks).map_err(|_| "Rotation too large".to_string())?;
+            rotations.push(Rotation {
+                direction: chosen,
+                clicks,
+            });
+            total += clicks as u64;
+            position = target;
+            if constraints.alternate {
+                direction = Some(match chosen {
+                    Direction::Left => Direction::Right,
+                    Direction::Right => Direction::Left,
+                });
+            }
+        }
+        Ok((total, rotations))
+    };
+
+    if constraints.alternate {
+        let left = plan(Some(Direction::Left))?;
+        let right = plan(Some(Direction::Right))?;
+        Ok(if left.0 <= right.0 { left.1 } else { right.1 })
+    } else {
+        Ok(plan(None)?.1)
+    }
+}
+
+// One rotation per line, can be read back with parse_rotations
+pub fn format_rotations(rotations: &[Rotation]) -> String {
+    rotations
+        .iter()
+        .map(|rotation| format!("{}\n", rotation))
+        .collect()
+}
+
+pub fn parse_rotations(input: &str) -> Vec<Rotation> {
+    try_parse_rotations(input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+// Rotations separated by newlines, commas or spaces, e.g. "L68, r30 +5 R10x3"
+// A repeat such as R10x3 gives three separate R10 rotations
+pub fn try_parse_rotations(input: &str) -> Result<Vec<Rotation>, String> {
+    let mut rotations = Vec::new();
+    for (line_number, line) in input.lines().enumerate() {
+        for token in rotation_tokens(line) {
+            let (rotation, repeat) = parse_repeated_rotation(token)
+                .map_err(|e| format!("Line {}, {}: {}", line_number + 1, token, e))?;
+            rotations.extend(std::iter::repeat_n(rotation, repeat as usize));
+        }
+    }
+    Ok(rotations)
+}
+
+// The rotations on one line, separated by commas or whitespace
+pub(crate) fn rotation_tokens(line: &str) -> impl Iterator<Item = &str> {
+    line.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+}
+
+// Largest repeat count, each repeat is stored as its own rotation so R10x4000000000
+// would run out of memory long before it was done
+pub const MAX_REPEAT: u32 = 1_000_000;
+
+// A rotation with an optional repeat count, R10x3 or R10
+pub(crate) fn parse_repeated_rotation(token: &str) -> Result<(Rotation, u32), String> {
+    match token.split_once(['x', 'X']) {
+        None => Ok((parse_rotation(token)?, 1)),
+        Some((_, count)) if count.contains(['x', 'X']) => Err("More than one repeat".to_string()),
+        Some((_, "")) => Err("Missing repeat count".to_string()),
+        Some((rotation, count)) => {
+            let rotation = parse_rotation(rotation)?;
+            let count = parse_number(count).map_err(|_| "Invalid repeat count".to_string())?;
+            if count == 0 {
+                return Err("Repeat count must be at least 1".to_string());
+            }
+            if count > MAX_REPEAT {
+                return Err(format!(
+                    "Repeat count {} is more than the limit of {}",
+                    count, MAX_REPEAT
+                ));
+            }
+            Ok((rotation, count))
+        }
+    }
+}
+
+// Parse a single instruction such as L68 or R30, also l68, r30, -68 and +30
+pub fn parse_rotation(token: &str) -> Result<Rotation, String> {
+    let mut chars = token.chars();
+    let direction = match chars.next() {
+        Some('L' | 'l' | '-') => Direction::Left,
+        Some('R' | 'r' | '+') => Direction::Right,
+        _ => return Err("Invalid direction".to_string()),
+    };
+    let clicks_str = chars.as_str();
+
+    if clicks_str.starts_with(is_direction) {
+        return Err("More than one direction".to_string());
+    }
+    // e.g. L5R3, which could be meant as one rotation or two
+    if clicks_str.contains(is_direction) {
+        return Err("Missing separator between rotations".to_string());
+    }
+    if clicks_str.contains(['x', 'X']) {
+        return Err("Repeat is only allowed in a list of rotations".to_string());
+    }
+
+    let clicks = parse_number(clicks_str).map_err(|_| "Invalid number of clicks".to_string())?;
+    Ok(Rotation { direction, clicks })
+}
+
+fn is_direction(c: char) -> bool {
+    matches!(c, 'L' | 'l' | 'R' | 'r' | '+' | '-')
+}
+
+// Digits only, str::parse would also take a leading +
+fn parse_number(text: &str) -> Result<u32, ()> {
+    if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(());
+    }
+    text.parse().map_err(|_| ())
+}
+
+impl Default for Lock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Run every rotation in the input, returns the total number of zero events
+pub fn solve(input: &str) -> u64 {
+    let rotations = parse_rotations(input);
+
+    let mut lock = Lock::new();
+    for rotation in &rotations {
+        lock.rotate(rotation);
+    }
+
+    lock.zero_passed_counter + lock.zero_finished_counter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rotations() {
+        let rotations = parse_rotations("L68\nR30\n");
+        assert_eq!(rotations.len(), 2);
+        assert!(matches!(rotations[0].direction, Direction::Left));
+        assert_eq!(rotations[0].clicks, 68);
+        assert!(matches!(rotations[1].direction, Direction::Right));
+        assert_eq!(rotations[1].clicks, 30);
+    }
+
+    #[test]
+    fn test_parse_rotation_errors() {
+        assert_eq!(parse_rotation("X10").unwrap_err(), "Invalid direction");
+        assert_eq!(parse_rotation("").unwrap_err(), "Invalid direction");
+        assert_eq!(
+            parse_rotation("L1a").unwrap_err(),
+            "Invalid number of clicks"
+        );
+    }
+
+    #[test]
+    fn test_parse_rotation_forms() {
+        let expected = |direction, clicks| Rotation { direction, clicks };
+        assert_eq!(parse_rotation("l68"), Ok(expected(Direction::Left, 68)));
+        assert_eq!(parse_rotation("r30"), Ok(expected(Direction::Right, 30)));
+        assert_eq!(parse_rotation("-68"), Ok(expected(Direction::Left, 68)));
+        assert_eq!(parse_rotation("+30"), Ok(expected(Direction::Right, 30)));
+
+        assert_eq!(
+            parse_rotation("L+5").unwrap_err(),
+            "More than one direction"
+        );
+        assert_eq!(
+            parse_rotation("+R5").unwrap_err(),
+            "More than one direction"
+        );
+        assert_eq!(
+            parse_rotation("L5R3").unwrap_err(),
+            "Missing separator between rotations"
+        );
+        assert_eq!(
+            parse_rotation("R10x5").unwrap_err(),
+            "Repeat is only allowed in a list of rotations"
+        );
+        assert_eq!(parse_rotation("L").unwrap_err(), "Invalid number of clicks");
+        assert_eq!(
+            parse_rotation("R99999999999").unwrap_err(),
+            "Invalid number of clicks"
+        );
+        assert_eq!(parse_rotation("30").unwrap_err(), "Invalid direction");
+    }
+
+    #[test]
+    fn test_try_parse_rotations() {
+        let rotations = try_parse_rotations("L68, r30\n\n+5 -2,R10x3\n").unwrap();
+        let clicks: Vec<u32> = rotations.iter().map(|r| r.clicks).collect();
+        assert_eq!(clicks, vec![68, 30, 5, 2, 10, 10, 10]);
+        assert_eq!(rotations[3].direction, Direction::Left);
+        assert_eq!(rotations[6].direction, Direction::Right);
+
+        // the same values as the plain one per line format
+        assert_eq!(
+            try_parse_rotations("l1 R2,-3 +4").unwrap(),
+            parse_rotations("L1\nR2\nL3\nR4\n")
+        );
+
+        assert_eq!(
+            try_parse_rotations("L1\nR2 R10x").unwrap_err(),
+            "Line 2, R10x: Missing repeat count"
+        );
+        assert_eq!(
+            try_parse_rotations("R10x0").unwrap_err(),
+            "Line 1, R10x0: Repeat count must be at least 1"
+        );
+        assert_eq!(
+            try_parse_rotations("R10x2x2").unwrap_err(),
+            "Line 1, R10x2x2: More than one repeat"
+        );
+        assert_eq!(
+            try_parse_rotations("R10x-2").unwrap_err(),
+            "Line 1, R10x-2: Invalid repeat count"
+        );
+        assert_eq!(
+            try_parse_rotations("R10x4000000000").unwrap_err(),
+            "Line 1, R10x4000000000: Repeat count 4000000000 is more than the limit of 1000000"
+        );
+        assert_eq!(try_parse_rotations("R1x1000000").unwrap().len(), 1_000_000);
+        assert_eq!(
+            try_parse_rotations("Rx2").unwrap_err(),
+            "Line 1, Rx2: Invalid number of clicks"
+        );
+    }
+
+    #[test]
+    fn test_rotate_counts_zeros() {
+        let mut lock = Lock::new();
+        lock.rotate(&Rotation {
+            direction: Direction::Left,
+            clicks: 50,
+        });
+        assert_eq!(lock.dial_position, 0);
+        assert_eq!(lock.zero_passed_counter, 1);
+        assert_eq!(lock.zero_finished_counter, 1);
+
+        lock.rotate(&Rotation {
+            direction: Direction::Right,
+            clicks: 250,
+        });
+        assert_eq!(lock.dial_position, 50);
+        assert_eq!(lock.zero_passed_counter, 3);
+        assert_eq!(lock.zero_finished_counter, 1);
+    }
+
+    #[test]
+    fn test_rotate_matches_stepwise() {
+        let starts = [0, 1, 2, 49, 50, 98, 99];
+        let clicks = [
+            0, 1, 2, 49, 50, 51, 98, 99, 100, 101, 199, 200, 201, 250, 1000,
+        ];
+        for &start in &starts {
+            for &clicks in &clicks {
+                for direction in [Direction::Left, Direction::Right] {
+                    let rotation = Rotation { direction, clicks };
+                    let mut fast = Lock::new();
+                    let mut slow = Lock::new();
+                    fast.dial_position = start;
+                    slow.dial_position = start;
+
+                    fast.rotate(&rotation);
+                    slow.rotate_stepwise(&rotation);
+                    assert_eq!(
+                        (
+                            fast.dial_position,
+                            fast.zero_passed_counter,
+                            fast.zero_finished_counter
+                        ),
+                        (
+                            slow.dial_position,
+                            slow.zero_passed_counter,
+                            slow.zero_finished_counter
+                        ),
+                        "start {} rotation {:?}",
+                        start,
+                        rotation
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotate_matches_stepwise_sequence() {
+        // simple LCG so the sequence is the same every run
+        let mut seed: u32 = 12345;
+        let mut fast = Lock::new();
+        let mut slow = Lock::new();
+        for _ in 0..2000 {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            let clicks = (seed >> 8) % 350;
+            let direction = if seed & 0x10000 == 0 {
+                Direction::Left
+            } else {
+                Direction::Right
+            };
+            let rotation = Rotation { direction, clicks };
+            fast.rotate(&rotation);
+            slow.rotate_stepwise(&rotation);
+            assert_eq!(fast.dial_position, slow.dial_position);
+        }
+        assert_eq!(fast.zero_passed_counter, slow.zero_passed_counter);
+        assert_eq!(fast.zero_finished_counter, slow.zero_finished_counter);
+    }
+
+    #[test]
+    fn test_rotate_large_clicks() {
+        let mut lock = Lock::new();
+        lock.rotate(&Rotation {
+            direction: Direction::Right,
+            clicks: 999_999_999,
+        });
+        assert_eq!(lock.dial_position, 49);
+        assert_eq!(lock.zero_passed_counter, 10_000_000);
+        assert_eq!(lock.zero_finished_counter, 0);
+    }
+
+    #[test]
+    fn test_counters_past_u32() {
+        // every click on a one position dial passes 0, two of these add up to more than u32::MAX
+        let dial = DialSpec::new(1, 0).unwrap();
+        let rotation = parse_rotation("R4000000000").unwrap();
+        let mut lock = Lock::with_dial(dial);
+        let mut watch_list = WatchList::parse("0", &dial).unwrap();
+        for _ in 0..2 {
+            lock.rotate_watching(&rotation, &mut watch_list);
+        }
+        assert_eq!(lock.zero_passed_counter, 8_000_000_000);
+        assert_eq!(lock.zero_finished_counter, 2);
+        assert_eq!(watch_list.get(0).unwrap().passed, 8_000_000_000);
+    }
+
+    // every start and click count up to two full turns against the stepwise version
+    fn check_dial_matches_stepwise(dial: DialSpec) {
+        for start in 0..dial.positions {
+            for clicks in 0..=dial.positions * 2 + 1 {
+                for direction in [Direction::Left, Direction::Right] {
+                    let rotation = Rotation { direction, clicks };
+                    let mut fast = Lock::with_dial(DialSpec::new(dial.positions, start).unwrap());
+                    let mut slow = Lock::with_dial(DialSpec::new(dial.positions, start).unwrap());
+
+                    fast.rotate(&rotation);
+                    slow.rotate_stepwise(&rotation);
+                    assert_eq!(
+                        (
+                            fast.dial_position,
+                            fast.zero_passed_counter,
+                            fast.zero_finished_counter
+                        ),
+                        (
+                            slow.dial_position,
+                            slow.zero_passed_counter,
+                            slow.zero_finished_counter
+                        ),
+                        "dial {:?} start {} rotation {:?}",
+                        dial,
+                        start,
+                        rotation
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_small_dials() {
+        check_dial_matches_stepwise(DialSpec::new(1, 0).unwrap());
+        check_dial_matches_stepwise(DialSpec::new(2, 0).unwrap());
+
+        // a single position dial lands on 0 with every click
+        let mut lock = Lock::with_dial(DialSpec::new(1, 0).unwrap());
+        lock.rotate(&Rotation {
+            direction: Direction::Left,
+            clicks: 5,
+        });
+        assert_eq!(lock.dial_position, 0);
+        assert_eq!(lock.zero_passed_counter, 5);
+        assert_eq!(lock.zero_finished_counter, 1);
+
+        let mut lock = Lock::with_dial(DialSpec::new(2, 1).unwrap());
+        lock.rotate(&Rotation {
+            direction: Direction::Right,
+            clicks: 3,
+        });
+        assert_eq!(lock.dial_position, 0);
+        assert_eq!(lock.zero_passed_counter, 2);
+    }
+
+    #[test]
+    fn test_other_dial_sizes() {
+        check_dial_matches_stepwise(DialSpec::new(7, 3).unwrap());
+        check_dial_matches_stepwise(DialSpec::new(40, 0).unwrap());
+
+        let mut lock = Lock::with_dial(DialSpec::new(40, 20).unwrap());
+        lock.rotate(&Rotation {
+            direction: Direction::Right,
+            clicks: 100,
+        });
+        assert_eq!(lock.dial_position, 0);
+        assert_eq!(lock.zero_passed_counter, 3);
+        assert_eq!(lock.zero_finished_counter, 1);
+    }
+
+    #[test]
+    fn test_dial_spec() {
+        assert_eq!(DialSpec::default(), DialSpec::new(100, 50).unwrap());
+        assert!(DialSpec::new(0, 0).is_err());
+        assert!(DialSpec::new(40, 40).is_err());
+
+        let dial = DialSpec::from_config("# variant\npositions = 40\nstart=5\n").unwrap();
+        assert_eq!(dial, DialSpec::new(40, 5).unwrap());
+        assert_eq!(
+            DialSpec::from_config("start = 10").unwrap(),
+            DialSpec::new(100, 10).unwrap()
+        );
+        assert!(DialSpec::from_config("size = 40").is_err());
+        assert!(DialSpec::from_config("positions = 10\nstart = 20").is_err());
+        assert!(DialSpec::from_config("positions").is_err());
+    }
+
+    #[test]
+    fn test_rotate_with_events() {
+        let mut lock = Lock::with_dial(DialSpec::new(4, 1).unwrap());
+        let mut events: Vec<LockEvent> = Vec::new();
+        lock.rotate_with(
+            &Rotation {
+                direction: Direction::Left,
+                clicks: 6,
+            },
+            &mut events,
+        );
+
+        assert_eq!(
+            events,
+            vec![
+                LockEvent::RotationStarted {
+                    position: 1,
+                    direction: Direction::Left,
+                    clicks: 6,
+                },
+                LockEvent::Passed {
+                    position: 0,
+                    times: 2,
+                },
+                LockEvent::Passed {
+                    position: 3,
+                    times: 2,
+                },
+                LockEvent::Passed {
+                    position: 2,
+                    times: 1,
+                },
+                LockEvent::Passed {
+                    position: 1,
+                    times: 1,
+                },
+                LockEvent::FullRevolution { count: 1 },
+                LockEvent::Landed { position: 3 },
+            ]
+        );
+        assert_eq!(lock.dial_position, 3);
+        assert_eq!(lock.zero_passed_counter, 2);
+    }
+
+    // Counts how often any position is reached, without touching Lock
+    struct PositionCounts(Vec<u64>);
+
+    impl LockObserver for PositionCounts {
+        fn on_event(&mut self, event: &LockEvent) {
+            if let LockEvent::Passed { position, times } = event {
+                self.0[*position as usize] += *times as u64;
+            }
+        }
+    }
+
+    #[test]
+    fn test_observer_matches_stepwise() {
+        let rotations = parse_rotations("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\nR1000\n");
+        let mut lock = Lock::new();
+        let mut counts = PositionCounts(vec![0; 100]);
+
+        // count every position reached one click at a time
+        let mut position = lock.dial_position;
+        let mut expected = vec![0; 100];
+        for rotation in &rotations {
+            for _ in 0..rotation.clicks {
+                position = match rotation.direction {
+                    Direction::Left => (position + 99) % 100,
+                    Direction::Right => (position + 1) % 100,
+                };
+                expected[position as usize] += 1;
+            }
+            lock.rotate_with(rotation, &mut counts);
+        }
+
+        assert_eq!(counts.0, expected);
+        assert_eq!(counts.0[0], lock.zero_passed_counter);
+        assert_eq!(lock.dial_position, position);
+    }
+
+    #[test]
+    fn test_watch_list() {
+        let dial = DialSpec::default();
+        let rotations = parse_rotations("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\nR1000\n");
+        let mut watch_list = WatchList::parse("0, 25,50,75,25", &dial).unwrap();
+        assert_eq!(watch_list.watches.len(), 4);
+
+        // the event stream gives the same pass counts one position at a time
+        let mut lock = Lock::new();
+        let mut counts = PositionCounts(vec![0; 100]);
+        let mut landed = vec![0; 100];
+        for rotation in &rotations {
+            lock.rotate_with(rotation, &mut counts);
+            landed[lock.dial_position as usize] += 1;
+        }
+
+        let mut lock = Lock::new();
+        for rotation in &rotations {
+            lock.rotate_watching(rotation, &mut watch_list);
+        }
+        for watch in &watch_list.watches {
+            assert_eq!(watch.passed, counts.0[watch.position as usize]);
+            assert_eq!(watch.landed, landed[watch.position as usize]);
+        }
+
+        let zero = watch_list.get(0).unwrap();
+        assert_eq!(zero.passed, lock.zero_passed_counter);
+        assert_eq!(zero.landed, lock.zero_finished_counter);
+        assert!(
+            watch_list
+                .report()
+                .starts_with("position 0: passed 16, landed 3\n")
+        );
+    }
+
+    #[test]
+    fn test_watch_list_errors() {
+        let dial = DialSpec::new(40, 0).unwrap();
+        assert!(WatchList::parse("0,39", &dial).is_ok());
+        assert!(WatchList::parse("0,40", &dial).is_err());
+        assert!(WatchList::parse("0,x", &dial).is_err());
+    }
+
+    // Cheapest total over every direction and up to two extra turns per rotation
+    fn brute_force_combination(
+        dial: &DialSpec,
+        start: u32,
+        targets: &[u32],
+        constraints: &SearchConstraints,
+        previous: Option<Direction>,
+    ) -> Option<u64> {
+        let Some((&target, rest)) = targets.split_first() else {
+            return Some(0);
+        };
+        let mut best: Option<u64> = None;
+        for direction in [Direction::Left, Direction::Right] {
+            if constraints.alternate && previous == Some(direction) {
+                continue;
+            }
+            for clicks in constraints.min_clicks..constraints.min_clicks + dial.positions * 3 {
+                let rotation = Rotation { direction, clicks };
+                if dial.position_after(start, &rotation) != target {
+                    continue;
+                }
+                if let Some(cost) =
+                    brute_force_combination(dial, target, rest, constraints, Some(direction))
+                {
+                    let total = cost + clicks as u64;
+                    best = Some(best.map_or(total, |best| best.min(total)));
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn test_find_combination() {
+        let dial = DialSpec::default();
+        let rotations =
+            find_combination(&dial, 50, &[0, 90, 10], &SearchConstraints::default()).unwrap();
+        assert_eq!(format_rotations(&rotations), "L50\nL10\nR20\n");
+
+        let constraints = SearchConstraints {
+            alternate: true,
+            min_clicks: 0,
+        };
+        let rotations = find_combination(&dial, 50, &[0, 90, 10], &constraints).unwrap();
+        assert_eq!(format_rotations(&rotations), "R50\nL10\nR20\n");
+
+        // round trips through the parser and lands on every target
+        let mut lock = Lock::new();
+        for rotation in parse_rotations(&format_rotations(&rotations)) {
+            lock.rotate(&rotation);
+        }
+        assert_eq!(lock.dial_position, 10);
+
+        assert!(find_combination(&dial, 50, &[100], &SearchConstraints::default()).is_err());
+        assert!(
+            find_combination(&dial, 50, &[], &SearchConstraints::default())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_find_combination_matches_brute_force() {
+        let dial = DialSpec::new(5, 0).unwrap();
+        let target_lists: [&[u32]; 5] = [&[3], &[0, 0], &[1, 4, 2], &[4, 1, 1, 3], &[2, 3, 0]];
+        for alternate in [false, true] {
+            for min_clicks in [0, 1, 3, 7] {
+                let constraints = SearchConstraints {
+                    alternate,
+                    min_clicks,
+                };
+                for start in 0..5 {
+                    for targets in target_lists {
+                        let rotations =
+                            find_combination(&dial, start, targets, &constraints).unwrap();
+                        let total: u64 = rotations.iter().map(|r| r.clicks as u64).sum();
+                        assert_eq!(
+                            Some(total),
+                            brute_force_combination(&dial, start, targets, &constraints, None),
+                            "start {} targets {:?} {:?}",
+                            start,
+                            targets,
+                            constraints
+                        );
+                        assert!(rotations.iter().all(|r| r.clicks >= min_clicks));
+                        if alternate {
+                            assert!(
+                                rotations
+                                    .windows(2)
+                                    .all(|w| w[0].direction != w[1].direction)
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve("L50\nR100\nL1\n"), 4);
+    }
+}
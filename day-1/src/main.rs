@@ -1,118 +1,107 @@
-#[derive(Debug)]
-enum Direction {
-    Left,
-    Right,
-}
-
-#[derive(Debug)]
-struct Rotation {
-    // Direction to rotate
-    direction: Direction,
-    // Number of clicks to rotate
-    clicks: u32,
-}
+use aoc_common::args::Args;
+use day_1::multi::{MultiLock, parse_addressed_rotations};
+use day_1::normalize::{folded_rotations, hidden_totals, normalize};
+use day_1::stats::compute_stats;
+use day_1::{
+    DialSpec, Lock, SearchConstraints, WatchList, find_combination, format_rotations,
+    parse_rotations,
+};
 
-#[derive(Debug)]
-struct Lock {
-    // Current position of the lock (0-99)
-    dial_position: u32,
-    // zero-counter (how many times we've passed 0)
-    zero_passed_counter: u32,
-    // Number of times the dial has landed on 0
-    zero_finished_counter: u32,
+// Dial from --config, with --dial-size and --dial-start taking priority
+fn dial_from_args(args: &Args) -> DialSpec {
+    let base = match args.value("--config") {
+        Some(path) => {
+            let text = std::fs::read_to_string(path).expect("Failed to read config file");
+            DialSpec::from_config(&text).unwrap_or_else(|e| panic!("{}", e))
+        }
+        None => DialSpec::default(),
+    };
+    DialSpec::new(
+        args.parsed("--dial-size").unwrap_or(base.positions),
+        args.parsed("--dial-start").unwrap_or(base.start),
+    )
+    .unwrap_or_else(|e| panic!("{}", e))
 }
 
-impl Lock {
-    fn new() -> Self {
-        Lock {
-            dial_position: 50,
-            zero_passed_counter: 0,
-            zero_finished_counter: 0,
-        }
-    }
+fn main() {
+    let args = Args::from_env();
 
-    // Rotate the lock one click to the right
-    fn rotate_one_right(&mut self) {
-        if self.dial_position == 99 {
-            self.dial_position = 0;
-            // Increment zero_passed_counter when passing 0
-            self.zero_passed_counter += 1;
-        } else {
-            self.dial_position += 1;
-        }
-    }
-    // Rotate the lock one click to the left
-    fn rotate_one_left(&mut self) {
-        if self.dial_position == 0 {
-            self.dial_position = 99;
-        } else if self.dial_position == 1 {
-            self.dial_position = 0;
-            // Increment zero_passed_counter when passing 0
-            self.zero_passed_counter += 1;
-        } else {
-            self.dial_position -= 1;
-        }
+    // e.g. --find 7,30,12 --alternate --min-clicks 10, prints the rotations instead of running the input
+    if let Some(targets) = args.value("--find") {
+        let dial = dial_from_args(&args);
+        let targets: Vec<u32> = targets
+            .split(',')
+            .map(|t| t.trim().parse().expect("Invalid target position"))
+            .collect();
+        let constraints = SearchConstraints {
+            alternate: args.flag("--alternate"),
+            min_clicks: args.parsed("--min-clicks").unwrap_or(0),
+        };
+        let rotations = find_combination(&dial, dial.start, &targets, &constraints)
+            .unwrap_or_else(|e| panic!("{}", e));
+        print!("{}", format_rotations(&rotations));
+        return;
     }
 
-    fn rotate(&mut self, rotation: &Rotation) {
-        match rotation.direction {
-            Direction::Left => {
-                for _ in 0..rotation.clicks {
-                    self.rotate_one_left();
-                }
-            }
-            Direction::Right => {
-                for _ in 0..rotation.clicks {
-                    self.rotate_one_right();
-                }
-            }
-        }
-        println!(
-            "Rotated {:?} by {} clicks to position {}",
-            rotation.direction, rotation.clicks, self.dial_position
-        );
+    let input_path = "./input.txt";
+    let input = std::fs::read_to_string(input_path).expect("Failed to read input file");
 
-        if self.dial_position == 0 {
-            self.zero_finished_counter += 1;
-            println!(
-                "Rotation ended on 0, incrementing counter to {}",
-                self.zero_finished_counter
-            );
+    // e.g. --dials 3, the input can then address a dial with 2:R15
+    if let Some(count) = args.parsed::<usize>("--dials") {
+        let mut multi_lock = MultiLock::new(&vec![dial_from_args(&args); count]);
+        let rotations = parse_addressed_rotations(&input).unwrap_or_else(|e| panic!("{}", e));
+        for (dial, rotation) in rotations {
+            multi_lock
+                .rotate(dial, &rotation)
+                .unwrap_or_else(|e| panic!("{}", e));
         }
+        println!("{}", multi_lock.report());
+        return;
     }
-}
-
-fn parse_rotations(input: &str) -> Vec<Rotation> {
-    input
-        .lines()
-        .map(|line| {
-            println!("Parsing line: {}", line);
-            let direction_str = &line[0..1];
-            let clicks_str = &line[1..];
 
-            let direction = match direction_str {
-                "L" => Direction::Left,
-                "R" => Direction::Right,
-                _ => panic!("Invalid direction"),
-            };
-
-            let clicks: u32 = clicks_str.parse().expect("Invalid number of clicks");
+    let rotations = parse_rotations(&input);
 
-            Rotation { direction, clicks }
-        })
-        .collect()
-}
+    let dial = dial_from_args(&args);
 
-fn main() {
-    let input_path = "./input.txt";
-    let input = std::fs::read_to_string(input_path).expect("Failed to read input file");
+    if args.flag("--stats") {
+        println!("{}", compute_stats(&dial, &rotations).report());
+        return;
+    }
 
-    let rotations = parse_rotations(&input);
+    // e.g. --normalize, prints the shorter program and what it no longer counts
+    // --fold 10 folds at most 10 rotations together, by default the whole program is folded
+    if args.flag("--normalize") {
+        let folds = normalize(&dial, dial.start, &rotations, args.parsed("--fold"));
+        print!("{}", format_rotations(&folded_rotations(&folds)));
+        let (passes, landings) = hidden_totals(&folds);
+        eprintln!(
+            "{} rotations folded into {}, hiding {} zero passes and {} zero landings",
+            rotations.len(),
+            folds.len(),
+            passes,
+            landings
+        );
+        return;
+    }
+    let lock = &mut Lock::with_dial(dial);
+    // e.g. --history trajectory.csv
+    let history_path = args.value("--history");
+    if history_path.is_some() {
+        lock.enable_history();
+    }
 
-    let lock = &mut Lock::new();
+    // e.g. --watch 0,25,50,75
+    let mut watch_list = match args.value("--watch") {
+        Some(text) => WatchList::parse(text, &dial).unwrap_or_else(|e| panic!("{}", e)),
+        None => WatchList::new(&[], &dial).expect("An empty watch list is always valid"),
+    };
 
     for rotation in &rotations {
-        lock.rotate(rotation);
+        lock.rotate_watching(rotation, &mut watch_list);
+        println!(
+            "Rotated {:?} by {} clicks to position {}",
+            rotation.direction, rotation.clicks, lock.dial_position
+        );
     }
 
     println!(
@@ -122,4 +111,12 @@ fn main() {
         lock.zero_finished_counter,
         lock.zero_passed_counter + lock.zero_finished_counter
     );
+
+    if !watch_list.watches.is_empty() {
+        println!("{}", watch_list.report());
+    }
+
+    if let (Some(path), Some(history)) = (history_path, &lock.history) {
+        std::fs::write(path, history.to_csv()).expect("Failed to write history");
+    }
 }
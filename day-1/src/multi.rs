@@ -0,0 +1,221 @@
+use crate::{
+    DialSpec, Direction, Lock, Rotation, parse_repeated_rotation, parse_rotation, rotation_tokens,
+};
+
+// Several dials where each one drives the next, like an odometer.
+// Every full revolution of a dial turns the next dial one click in the same direction:
+// turning right carries when a dial wraps from its last position to 0, turning left
+// borrows when it wraps from 0 to its last position. Only the dial that was turned
+// counts zero passes and landings, carried clicks just move the dials they drive.
+#[derive(Debug)]
+pub struct MultiLock {
+    pub dials: Vec<Lock>,
+}
+
+impl MultiLock {
+    pub fn new(dials: &[DialSpec]) -> Self {
+        MultiLock {
+            dials: dials.iter().map(|&dial| Lock::with_dial(dial)).collect(),
+        }
+    }
+
+    pub fn rotate(&mut self, dial: usize, rotation: &Rotation) -> Result<(), String> {
+        if dial >= self.dials.len() {
+            return Err(format!(
+                "No dial {}, the lock has {} dials",
+                dial,
+                self.dials.len()
+            ));
+        }
+
+        let mut turn = *rotation;
+        for (index, lock) in self.dials.iter_mut().enumerate().skip(dial) {
+            let carry = completed_revolutions(lock, &turn);
+            if index == dial {
+                lock.rotate(&turn);
+            } else {
+                lock.dial_position = lock.dial.position_after(lock.dial_position, &turn);
+            }
+            if carry == 0 {
+                break;
+            }
+            turn.clicks = carry;
+        }
+        Ok(())
+    }
+
+    pub fn positions(&self) -> Vec<u32> {
+        self.dials.iter().map(|lock| lock.dial_position).collect()
+    }
+
+    // One line per dial
+    pub fn report(&self) -> String {
+        self.dials
+            .iter()
+            .enumerate()
+            .map(|(i, lock)| {
+                format!(
+                    "dial {}: position {}, zero_passed_counter {}, zero_finished_counter {}",
+                    i, lock.dial_position, lock.zero_passed_counter, lock.zero_finished_counter
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+// Wraps past the end of the dial during the rotation, the carry into the next dial
+fn completed_revolutions(lock: &Lock, rotation: &Rotation) -> u32 {
+    let last = lock.dial.positions - 1;
+    // the wrap is the click that arrives on 0 going right, or on the last position going left
+    let target = match rotation.direction {
+        Direction::Right => 0,
+        Direction::Left => last,
+    };
+    // one wrap at most per click, so this fits whenever the clicks do
+    lock.dial
+        .count_arrivals(lock.dial_position, target, rotation) as u32
+}
+
+// A rotation for one dial such as 2:R15, without a prefix it turns dial 0
+pub fn parse_addressed_rotation(line: &str) -> Result<(usize, Rotation), String> {
+    match line.split_once(':') {
+        Some((dial, rotation)) => Ok((parse_dial_index(dial)?, parse_rotation(rotation.trim())?)),
+        None => Ok((0, parse_rotation(line)?)),
+    }
+}
+
+// Addressed rotations in the same format as try_parse_rotations, e.g. "2:r15, 1:L3x2 +4"
+pub fn parse_addressed_rotations(input: &str) -> Result<Vec<(usize, Rotation)>, String> {
+    let mut rotations = Vec::new();
+    for (line_number, line) in input.lines().enumerate() {
+        // keeps "2: R15" together as one token
+        let line = line
+            .split(':')
+            .map(str::trim)
+            .collect::<Vec<&str>>()
+            .join(":");
+        for token in rotation_tokens(&line) {
+            let (dial, rotation, repeat) = parse_addressed_token(token)
+                .map_err(|e| format!("Line {}, {}: {}", line_number + 1, token, e))?;
+            rotations.extend(std::iter::repeat_n((dial, rotation), repeat as usize));
+        }
+    }
+    Ok(rotations)
+}
+
+// Dial, rotation and repeat count of a token such as 2:R15x3
+fn parse_addressed_token(token: &str) -> Result<(usize, Rotation, u32), String> {
+    let (dial, rotation) = match token.split_once(':') {
+        Some((dial, rotation)) => (parse_dial_index(dial)?, rotation),
+        None => (0, token),
+    };
+    let (rotation, repeat) = parse_repeated_rotation(rotation)?;
+    Ok((dial, rotation, repeat))
+}
+
+fn parse_dial_index(text: &str) -> Result<usize, String> {
+    text.trim()
+        .parse()
+        .map_err(|_| "Invalid dial index".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decimal_lock(dials: usize) -> MultiLock {
+        MultiLock::new(&vec![DialSpec::new(10, 0).unwrap(); dials])
+    }
+
+    #[test]
+    fn test_parse_addressed_rotation() {
+        let (dial, rotation) = parse_addressed_rotation("2:R15").unwrap();
+        assert_eq!(dial, 2);
+        assert_eq!(rotation.direction, Direction::Right);
+        assert_eq!(rotation.clicks, 15);
+
+        assert_eq!(parse_addressed_rotation("L3").unwrap().0, 0);
+        assert_eq!(
+            parse_addressed_rotation("x:L3").unwrap_err(),
+            "Invalid dial index"
+        );
+        assert_eq!(
+            parse_addressed_rotation("1:Q3").unwrap_err(),
+            "Invalid direction"
+        );
+    }
+
+    #[test]
+    fn test_parse_addressed_rotations() {
+        // the same syntax as try_parse_rotations, blank lines are skipped
+        let rotations = parse_addressed_rotations("2:r15, 1: -3x2\n\n+4\n").unwrap();
+        let expected = [(2, "R15"), (1, "L3"), (1, "L3"), (0, "R4")]
+            .map(|(dial, rotation)| (dial, parse_rotation(rotation).unwrap()));
+        assert_eq!(rotations, expected);
+
+        assert_eq!(
+            parse_addressed_rotations("R1\nx:L3").unwrap_err(),
+            "Line 2, x:L3: Invalid dial index"
+        );
+        assert_eq!(
+            parse_addressed_rotations("1:R10x0").unwrap_err(),
+            "Line 1, 1:R10x0: Repeat count must be at least 1"
+        );
+    }
+
+    #[test]
+    fn test_carry_like_an_odometer() {
+        let mut lock = decimal_lock(3);
+        for (dial, rotation) in parse_addressed_rotations("R7\nR5\n0:R998\n").unwrap() {
+            lock.rotate(dial, &rotation).unwrap();
+        }
+        // 1010 clicks on the first dial reads as 1010 on the wheels, last dial on the left
+        assert_eq!(lock.positions(), vec![0, 1, 0]);
+        assert_eq!(lock.dials[0].zero_passed_counter, 101);
+        // carried clicks move the other dials without counting as their own
+        assert_eq!(lock.dials[1].zero_passed_counter, 0);
+        assert_eq!(lock.dials[2].zero_finished_counter, 0);
+
+        // borrowing back down again, 1010 - 11 is 999
+        lock.rotate(0, &parse_rotation("L11").unwrap()).unwrap();
+        assert_eq!(lock.positions(), vec![9, 9, 9]);
+    }
+
+    #[test]
+    fn test_carry_on_wraps_only() {
+        // the default dials start at 50, the carry is the step from 99 to 0 and nothing else
+        let mut lock = MultiLock::new(&[DialSpec::default(), DialSpec::default()]);
+        lock.rotate(0, &parse_rotation("R49").unwrap()).unwrap();
+        assert_eq!(lock.positions(), vec![99, 50]);
+        lock.rotate(0, &parse_rotation("R1").unwrap()).unwrap();
+        assert_eq!(lock.positions(), vec![0, 51]);
+
+        // going left the borrow is the step from 0 to 99, not from 1 to 0
+        lock.rotate(0, &parse_rotation("L1").unwrap()).unwrap();
+        assert_eq!(lock.positions(), vec![99, 50]);
+        lock.rotate(0, &parse_rotation("L99").unwrap()).unwrap();
+        assert_eq!(lock.positions(), vec![0, 50]);
+        lock.rotate(0, &parse_rotation("L250").unwrap()).unwrap();
+        assert_eq!(lock.positions(), vec![50, 47]);
+        assert_eq!(lock.dials[1].zero_passed_counter, 0);
+    }
+
+    #[test]
+    fn test_rotate_later_dial() {
+        let mut lock = decimal_lock(2);
+        for (dial, rotation) in parse_addressed_rotations("1:L3\n0:L1\n").unwrap() {
+            lock.rotate(dial, &rotation).unwrap();
+        }
+        // L3 on dial 1 only, then dial 0 wraps from 0 to 9 and borrows a click from dial 1
+        assert_eq!(lock.positions(), vec![9, 6]);
+        assert_eq!(
+            lock.report(),
+            "dial 0: position 9, zero_passed_counter 0, zero_finished_counter 0\n\
+             dial 1: position 6, zero_passed_counter 0, zero_finished_counter 0"
+        );
+
+        let rotation = parse_rotation("R1").unwrap();
+        assert!(lock.rotate(2, &rotation).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+use std::ops::Range;
+
+use crate::{DialSpec, Direction, Lock, Rotation};
+
+// Several input rotations replaced by their net movement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fold {
+    // Indices of the input rotations this replaces
+    pub source: Range<usize>,
+    pub rotation: Rotation,
+    // Zero events the input rotations count that the folded one doesn't, never negative
+    // since the net movement is a subset of the path the input rotations took
+    pub hidden_passes: u64,
+    pub hidden_landings: u64,
+}
+
+// Fold the rotations into net rotations with fewer than dial.positions clicks, starting
+// from `start`. Without a limit the whole program becomes one rotation, otherwise every
+// `max_fold` consecutive rotations are folded together and a limit of 1 only reduces the clicks.
+pub fn normalize(
+    dial: &DialSpec,
+    start: u32,
+    rotations: &[Rotation],
+    max_fold: Option<usize>,
+) -> Vec<Fold> {
+    let size = dial.positions as i64;
+    let fold_size = max_fold.unwrap_or(rotations.len()).max(1);
+    let mut folds = Vec::new();
+    let mut lock = Lock::with_dial(DialSpec { start, ..*dial });
+
+    for (index, chunk) in rotations.chunks(fold_size).enumerate() {
+        let first = index * fold_size;
+        let fold_start = lock.dial_position;
+
+        let before = lock.state();
+        let mut net: i64 = 0;
+        for rotation in chunk {
+            lock.rotate(rotation);
+            net += match rotation.direction {
+                Direction::Left => -(rotation.clicks as i64),
+                Direction::Right => rotation.clicks as i64,
+            };
+        }
+        let after = lock.state();
+
+        let rotation = Rotation {
+            direction: if net < 0 {
+                Direction::Left
+            } else {
+                Direction::Right
+            },
+            clicks: (net.abs() % size) as u32,
+        };
+        let mut folded = Lock::with_dial(DialSpec {
+            start: fold_start,
+            ..*dial
+        });
+        folded.rotate(&rotation);
+
+        folds.push(Fold {
+            source: first..first + chunk.len(),
+            rotation,
+            hidden_passes: (after.zero_passed_counter - before.zero_passed_counter)
+                - folded.zero_passed_counter,
+            hidden_landings: (after.zero_finished_counter - before.zero_finished_counter)
+                - folded.zero_finished_counter,
+        });
+    }
+    folds
+}
+
+pub fn folded_rotations(folds: &[Fold]) -> Vec<Rotation> {
+    folds.iter().map(|fold| fold.rotation).collect()
+}
+
+// Totals over every fold, (hidden passes, hidden landings)
+pub fn hidden_totals(folds: &[Fold]) -> (u64, u64) {
+    folds.iter().fold((0, 0), |(passes, landings), fold| {
+        (passes + fold.hidden_passes, landings + fold.hidden_landings)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_rotations;
+
+    fn run(rotations: &[Rotation]) -> Lock {
+        let mut lock = Lock::new();
+        for rotation in rotations {
+            lock.rotate(rotation);
+        }
+        lock
+    }
+
+    #[test]
+    fn test_reduce_clicks() {
+        let rotations = parse_rotations("R250\nL101\n");
+        let folds = normalize(&DialSpec::default(), 50, &rotations, Some(1));
+        assert_eq!(
+            folds[0].rotation,
+            Rotation {
+                direction: Direction::Right,
+                clicks: 50
+            }
+        );
+        assert_eq!((folds[0].hidden_passes, folds[0].hidden_landings), (2, 0));
+        assert_eq!(
+            folds[1].rotation,
+            Rotation {
+                direction: Direction::Left,
+                clicks: 1
+            }
+        );
+        assert_eq!((folds[1].hidden_passes, folds[1].hidden_landings), (1, 0));
+    }
+
+    #[test]
+    fn test_cancelling_rotations() {
+        let rotations = parse_rotations("R50\nL50\nR60\nL60\n");
+        let folds = normalize(&DialSpec::default(), 50, &rotations, None);
+        assert_eq!(folds.len(), 1);
+        assert_eq!(folds[0].source, 0..4);
+        assert_eq!(folds[0].rotation.clicks, 0);
+        // R50 lands on 0, then R60 and L60 each pass it
+        assert_eq!((folds[0].hidden_passes, folds[0].hidden_landings), (3, 1));
+
+        // a limit cuts the program into pieces, R50 and L50 no longer cancel out
+        let folds = normalize(&DialSpec::default(), 50, &rotations, Some(3));
+        assert_eq!(folds.len(), 2);
+        assert_eq!(folds[0].rotation.clicks, 60);
+        assert_eq!(folds[1].source, 3..4);
+        assert!(normalize(&DialSpec::default(), 50, &[], None).is_empty());
+    }
+
+    #[test]
+    fn test_equivalent_under_both_counts() {
+        let rotations =
+            parse_rotations("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\nR1000\nL350\n");
+        let original = run(&rotations);
+
+        for max_fold in [1, 2, 3, 5, 12, 100].map(Some).into_iter().chain([None]) {
+            let folds = normalize(&DialSpec::default(), 50, &rotations, max_fold);
+            let folded = run(&folded_rotations(&folds));
+            let (passes, landings) = hidden_totals(&folds);
+
+            assert_eq!(folded.dial_position, original.dial_position);
+            assert_eq!(
+                folded.zero_passed_counter + passes,
+                original.zero_passed_counter
+            );
+            assert_eq!(
+                folded.zero_finished_counter + landings,
+                original.zero_finished_counter
+            );
+            assert!(folds.iter().all(|fold| fold.rotation.clicks < 100));
+        }
+    }
+}
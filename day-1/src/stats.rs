@@ -0,0 +1,137 @@
+use crate::{DialSpec, Direction, Lock, Rotation};
+
+// Summary of a rotation sequence, see compute_stats
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RotationStats {
+    pub rotations: usize,
+    // How many rotations ended on each position, indexed by position
+    pub landings: Vec<u32>,
+    // Most rotations in a row that neither pass nor land on 0
+    pub longest_without_zero: usize,
+    pub left_clicks: u64,
+    pub right_clicks: u64,
+    // First of the largest rotations
+    pub max_rotation: Option<Rotation>,
+    // Rotations of at least one whole turn
+    pub full_revolutions: usize,
+}
+
+// Everything in one pass over the rotations
+pub fn compute_stats(dial: &DialSpec, rotations: &[Rotation]) -> RotationStats {
+    let mut lock = Lock::with_dial(*dial);
+    let mut stats = RotationStats {
+        rotations: rotations.len(),
+        landings: vec![0; dial.positions as usize],
+        longest_without_zero: 0,
+        left_clicks: 0,
+        right_clicks: 0,
+        max_rotation: None,
+        full_revolutions: 0,
+    };
+    let mut stretch = 0;
+
+    for rotation in rotations {
+        let passed_before = lock.zero_passed_counter;
+        lock.rotate(rotation);
+        stats.landings[lock.dial_position as usize] += 1;
+
+        // landing on 0 is also a pass, so this covers both
+        if lock.zero_passed_counter == passed_before {
+            stretch += 1;
+            stats.longest_without_zero = stats.longest_without_zero.max(stretch);
+        } else {
+            stretch = 0;
+        }
+
+        match rotation.direction {
+            Direction::Left => stats.left_clicks += rotation.clicks as u64,
+            Direction::Right => stats.right_clicks += rotation.clicks as u64,
+        }
+        if stats
+            .max_rotation
+            .is_none_or(|max| rotation.clicks > max.clicks)
+        {
+            stats.max_rotation = Some(*rotation);
+        }
+        if rotation.clicks >= dial.positions {
+            stats.full_revolutions += 1;
+        }
+    }
+    stats
+}
+
+impl RotationStats {
+    pub fn report(&self) -> String {
+        let mut lines = vec![
+            format!("Rotations: {}", self.rotations),
+            format!(
+                "Clicks: {} left, {} right",
+                self.left_clicks, self.right_clicks
+            ),
+            match self.max_rotation {
+                Some(rotation) => format!("Largest rotation: {}", rotation),
+                None => "Largest rotation: none".to_string(),
+            },
+            format!(
+                "Rotations with a full revolution: {}",
+                self.full_revolutions
+            ),
+            format!(
+                "Longest stretch without touching 0: {} rotations",
+                self.longest_without_zero
+            ),
+            "Landings:".to_string(),
+        ];
+        for (position, &count) in self.landings.iter().enumerate() {
+            if count > 0 {
+                lines.push(format!("  {:>3}: {}", position, count));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_rotations;
+
+    #[test]
+    fn test_compute_stats() {
+        let rotations = parse_rotations("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n");
+        let stats = compute_stats(&DialSpec::default(), &rotations);
+
+        assert_eq!(stats.rotations, 10);
+        assert_eq!((stats.left_clicks, stats.right_clicks), (340, 122));
+        assert_eq!(stats.max_rotation.unwrap().to_string(), "L99");
+        assert_eq!(stats.full_revolutions, 0);
+        // positions 82 52 0 95 55 0 99 0 14 32
+        assert_eq!(stats.landings[0], 3);
+        assert_eq!(stats.landings[32], 1);
+        assert_eq!(stats.landings.iter().sum::<u32>(), 10);
+        // every other rotation passes or lands on 0
+        assert_eq!(stats.longest_without_zero, 1);
+    }
+
+    #[test]
+    fn test_report() {
+        let rotations = parse_rotations("R150\nL20\n");
+        let report = compute_stats(&DialSpec::default(), &rotations).report();
+        assert_eq!(
+            report,
+            "Rotations: 2\n\
+             Clicks: 20 left, 150 right\n\
+             Largest rotation: R150\n\
+             Rotations with a full revolution: 1\n\
+             Longest stretch without touching 0: 1 rotations\n\
+             Landings:\n    \
+             0: 1\n   \
+             80: 1"
+        );
+        assert!(
+            compute_stats(&DialSpec::default(), &[])
+                .report()
+                .contains("none")
+        );
+    }
+}
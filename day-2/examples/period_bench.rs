@@ -0,0 +1,55 @@
+// Compares the string and numeric repeated digit checks
+// cargo run --release --example period_bench -- [count] [start]
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use day_2::period::{digit_count, has_period, minimal_period};
+use day_2::{has_repeated_advanced, has_repeated_digits};
+
+fn time(
+    label: &str,
+    ids: std::ops::Range<u128>,
+    check: impl Fn(u128) -> bool,
+) -> (usize, Duration) {
+    let started = Instant::now();
+    let matches = ids.filter(|&id| check(black_box(id))).count();
+    let elapsed = started.elapsed();
+    println!("{:<22} {:>8} matches in {:?}", label, matches, elapsed);
+    (matches, elapsed)
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let count: u128 = args
+        .first()
+        .map_or(5_000_000, |n| n.parse().expect("Invalid count"));
+    let start: u128 = args.get(1).map_or(1, |n| n.parse().expect("Invalid start"));
+    let ids = start..start + count;
+    println!("Checking {} ids from {}", count, start);
+
+    let (string_twice, string_time) = time("string, twice", ids.clone(), |id| {
+        let id = id.to_string();
+        id.len().is_multiple_of(2) && has_repeated_digits(id)
+    });
+    let (numeric_twice, numeric_time) = time("numeric, twice", ids.clone(), |id| {
+        let length = digit_count(id);
+        length.is_multiple_of(2) && has_period(id, length / 2)
+    });
+    assert_eq!(string_twice, numeric_twice);
+    println!(
+        "{:.1}x faster",
+        string_time.as_secs_f64() / numeric_time.as_secs_f64()
+    );
+
+    let (string_any, string_time) = time("string, at least twice", ids.clone(), |id| {
+        has_repeated_advanced(id.to_string())
+    });
+    let (numeric_any, numeric_time) = time("numeric, at least twice", ids, |id| {
+        minimal_period(id) < digit_count(id)
+    });
+    assert_eq!(string_any, numeric_any);
+    println!(
+        "{:.1}x faster",
+        string_time.as_secs_f64() / numeric_time.as_secs_f64()
+    );
+}
@@ -0,0 +1,226 @@
+use std::iter::FusedIterator;
+
+use crate::period::Radix;
+use crate::rule::Rule;
+use crate::{IdRange, length_bounds, pattern_bounds};
+
+// Invalid ids of a range in ascending order, worked out one at a time from whichever end is asked for
+pub struct InvalidIds<'a> {
+    rule: &'a Rule,
+    radix: Radix,
+    // Rule::periods for the id length at each end, only worked out again when the length changes
+    front_periods: Option<(u32, Option<Vec<u32>>)>,
+    back_periods: Option<(u32, Option<Vec<u32>>)>,
+    // ids not looked at yet, both ends included
+    front: u128,
+    back: u128,
+    done: bool,
+}
+
+impl<'a> InvalidIds<'a> {
+    pub(crate) fn new(range: &IdRange, rule: &'a Rule) -> Self {
+        InvalidIds {
+            rule,
+            radix: range.radix,
+            front_periods: None,
+            back_periods: None,
+            front: range.start,
+            back: range.end,
+            done: range.start > range.end,
+        }
+    }
+
+    // Jump ahead so the next id from the front is the first invalid id >= id
+    pub fn skip_to(&mut self, id: u128) {
+        if id > self.back {
+            self.done = true;
+        } else if id > self.front {
+            self.front = id;
+        }
+    }
+}
+
+// Rule::periods for length, reusing the cached ones if they are for the same length
+fn cached_periods<'c>(
+    cache: &'c mut Option<(u32, Option<Vec<u32>>)>,
+    rule: &Rule,
+    length: u32,
+) -> &'c Option<Vec<u32>> {
+    if cache.as_ref().is_none_or(|(cached, _)| *cached != length) {
+        *cache = Some((length, rule.periods(length)));
+    }
+    &cache.as_ref().expect("Just filled in").1
+}
+
+impl Iterator for InvalidIds<'_> {
+    type Item = u128;
+
+    fn next(&mut self) -> Option<u128> {
+        while !self.done {
+            let length = self.radix.digit_count(self.front);
+            let (low, high) = length_bounds(length, self.front, self.back, self.radix);
+            let found = match cached_periods(&mut self.front_periods, self.rule, length) {
+                Some(periods) => first_repeated(length, low, high, periods, self.radix),
+                None => (low..=high).find(|&id| self.rule.matches_in(id, self.radix)),
+            };
+            // carry on from just past the id, or from the next length if there wasn't one
+            let checked = found.unwrap_or(high);
+            if checked == self.back {
+                self.done = true;
+            } else {
+                self.front = checked + 1;
+            }
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+}
+
+impl DoubleEndedIterator for InvalidIds<'_> {
+    fn next_back(&mut self) -> Option<u128> {
+        while !self.done {
+            let length = self.radix.digit_count(self.back);
+            let (low, high) = length_bounds(length, self.front, self.back, self.radix);
+            let found = match cached_periods(&mut self.back_periods, self.rule, length) {
+                Some(periods) => last_repeated(length, low, high, periods, self.radix),
+                None => (low..=high)
+                    .rev()
+                    .find(|&id| self.rule.matches_in(id, self.radix)),
+            };
+            let checked = found.unwrap_or(low);
+            if checked == self.front {
+                self.done = true;
+            } else {
+                self.back = checked - 1;
+            }
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for InvalidIds<'_> {}
+
+// Smallest id of `length` digits in low..=high made of a repeated pattern with one of the periods
+fn first_repeated(
+    length: u32,
+    low: u128,
+    high: u128,
+    periods: &[u32],
+    radix: Radix,
+) -> Option<u128> {
+    periods
+        .iter()
+        .filter_map(|&period| {
+            let multiplier = radix.repeat_multiplier(period, length / period);
+            let (smallest, largest) = pattern_bounds(period, radix);
+            let pattern = low.div_ceil(multiplier).max(smallest);
+            // the longest patterns can repeat to more than u128 holds
+            (pattern <= largest)
+                .then(|| pattern.checked_mul(multiplier))
+                .flatten()
+                .filter(|&id| id <= high)
+        })
+        .min()
+}
+
+fn last_repeated(
+    length: u32,
+    low: u128,
+    high: u128,
+    periods: &[u32],
+    radix: Radix,
+) -> Option<u128> {
+    periods
+        .iter()
+        .filter_map(|&period| {
+            let multiplier = radix.repeat_multiplier(period, length / period);
+            let (smallest, largest) = pattern_bounds(period, radix);
+            let pattern = (high / multiplier).min(largest);
+            (pattern >= smallest)
+                .then(|| pattern * multiplier)
+                .filter(|&id| id >= low)
+        })
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::progress::NoProgress;
+
+    fn found(range: &IdRange, rule: &Rule) -> Vec<u128> {
+        let mut range = IdRange::with_radix(range.start, range.end, range.radix);
+        range.find_invalid_ids(rule, &mut NoProgress);
+        range.invalid_ids
+    }
+
+    #[test]
+    fn test_matches_find() {
+        let rules = [
+            Rule::Twice,
+            Rule::AtLeastTwice,
+            Rule::MinPatternLength(2),
+            Rule::parse("palindrome|exactly:3").unwrap(),
+        ];
+        let hex = Radix::new(16).unwrap();
+        let ranges = [
+            IdRange::new(0, 5000),
+            IdRange::new(95, 115),
+            IdRange::new(99990, 1000020),
+            IdRange::new(u128::MAX - 1000, u128::MAX),
+            IdRange::with_radix(0xAB00, 0xAC00, hex),
+        ];
+        for range in &ranges {
+            for rule in &rules {
+                let expected = found(range, rule);
+                let ids: Vec<u128> = range.invalid_ids(rule).collect();
+                assert_eq!(ids, expected, "{}-{} rule {}", range.start, range.end, rule);
+
+                let mut reversed: Vec<u128> = range.invalid_ids(rule).rev().collect();
+                reversed.reverse();
+                assert_eq!(reversed, expected);
+            }
+        }
+        assert_eq!(IdRange::new(20, 10).invalid_ids(&Rule::Twice).next(), None);
+
+        // both ends reach 0 for a rule that includes it
+        let ids: Vec<u128> = IdRange::new(0, 12)
+            .invalid_ids(&Rule::Palindrome)
+            .rev()
+            .collect();
+        assert_eq!(ids, [11, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_take_rev_and_skip() {
+        let range = IdRange::new(1, 10u128.pow(30));
+        let rule = Rule::AtLeastTwice;
+        let first: Vec<u128> = range.invalid_ids(&rule).take(12).collect();
+        assert_eq!(first, [11, 22, 33, 44, 55, 66, 77, 88, 99, 111, 222, 333]);
+        assert_eq!(
+            range.invalid_ids(&rule).next_back(),
+            Some(10u128.pow(30) - 1)
+        );
+
+        let mut ids = range.invalid_ids(&rule);
+        ids.skip_to(1000);
+        assert_eq!(ids.next(), Some(1010));
+        // ten on from 1010, after 1111 to 1919
+        assert_eq!(ids.nth(9), Some(2020));
+        ids.skip_to(10u128.pow(30));
+        assert_eq!(ids.next(), None);
+
+        // both ends meet in the middle without repeating an id
+        let mut ids = IdRange::new(95, 1012).invalid_ids(&rule);
+        assert_eq!(ids.next(), Some(99));
+        assert_eq!(ids.next_back(), Some(1010));
+        assert_eq!(ids.next_back(), Some(999));
+        let rest: Vec<u128> = ids.collect();
+        assert_eq!(rest, [111, 222, 333, 444, 555, 666, 777, 888]);
+    }
+}
@@ -0,0 +1,1042 @@
+pub mod ids;
+pub mod parallel;
+pub mod period;
+pub mod report;
+pub mod rule;
+pub mod wide;
+
+use aoc_common::progress::{NoProgress, Progress};
+use ids::InvalidIds;
+use period::Radix;
+use rule::Rule;
+use wide::{BigUint, OverflowPolicy};
+
+pub struct IdRange {
+    pub start: u128,
+    pub end: u128,
+    // The ids are written in this radix, the rules look at its digits
+    pub radix: Radix,
+    pub invalid_ids: Vec<u128>,
+}
+
+impl IdRange {
+    pub fn new(start: u128, end: u128) -> Self {
+        IdRange::with_radix(start, end, Radix::DECIMAL)
+    }
+
+    pub fn with_radix(start: u128, end: u128, radix: Radix) -> Self {
+        IdRange {
+            start,
+            end,
+            radix,
+            invalid_ids: Vec::new(),
+        }
+    }
+
+    fn from_str(range_str: &str, radix: Radix) -> Self {
+        let parts: Vec<&str> = range_str.split('-').collect();
+        let start = radix.parse(parts[0]);
+
+        let range_start = match start {
+            Err(range_start) => panic!("Invalid start range: {}", range_start),
+            Ok(range_start) => range_start,
+        };
+
+        let end = radix.parse(parts[1]);
+
+        let range_end = match end {
+            Err(range_end) => panic!("Invalid end range: {}", range_end),
+            Ok(range_end) => range_end,
+        };
+
+        IdRange::with_radix(range_start, range_end, radix)
+    }
+
+    // 0 for a reversed range such as 200-100
+    pub fn number_ids_in_range(&self) -> u128 {
+        id_count(self.start, self.end)
+    }
+
+    // Builds the invalid ids directly rather than checking every id in the range,
+    // only rules that can't be built from repeated patterns fall back to checking
+    pub fn find_invalid_ids(&mut self, rule: &Rule, progress: &mut dyn Progress) {
+        let radix = self.radix;
+        for length in radix.digit_count(self.start)..=radix.digit_count(self.end) {
+            let (low, high) = length_bounds(length, self.start, self.end, radix);
+            match rule.periods(length) {
+                Some(periods) => {
+                    self.invalid_ids
+                        .extend(generate_invalid_ids(length, low, high, &periods, radix));
+                    progress.advance(u64::try_from(id_count(low, high)).unwrap_or(u64::MAX));
+                }
+                None => self
+                    .invalid_ids
+                    .extend(scan_ids(low, high, rule, radix, progress)),
+            }
+        }
+    }
+
+    // Count and sum of the invalid ids without building the list, see series_terms
+    pub fn invalid_totals(&self, rule: &Rule) -> InvalidTotals {
+        let mut terms = Vec::new();
+        let mut scanned = InvalidTotals::default();
+        let radix = self.radix;
+        for length in radix.digit_count(self.start)..=radix.digit_count(self.end) {
+            let (low, high) = length_bounds(length, self.start, self.end, radix);
+            match rule.periods(length) {
+                Some(periods) => terms.extend(series_terms(length, low, high, &periods, radix)),
+                None => {
+                    for id in scan_ids(low, high, rule, radix, &mut NoProgress) {
+                        scanned = scanned.combine(&InvalidTotals {
+                            count: BigUint::from(1),
+                            sum: BigUint::from(id),
+                        });
+                    }
+                }
+            }
+        }
+        totals_u128(&terms)
+            .unwrap_or_else(|| {
+                let terms: Vec<WideTerm> = terms.iter().map(WideTerm::from).collect();
+                totals_wide(&terms)
+            })
+            .combine(&scanned)
+    }
+
+    // The invalid ids in ascending order, one at a time without building a list
+    pub fn invalid_ids<'a>(&self, rule: &'a Rule) -> InvalidIds<'a> {
+        InvalidIds::new(self, rule)
+    }
+
+    // Reference implementation, checks every id in the range one at a time
+    pub fn scan_invalid_ids(&mut self, rule: &Rule, progress: &mut dyn Progress) {
+        self.invalid_ids
+            .extend(scan_ids(self.start, self.end, rule, self.radix, progress));
+    }
+}
+
+// Check every id from low to high
+fn scan_ids(
+    low: u128,
+    high: u128,
+    rule: &Rule,
+    radix: Radix,
+    progress: &mut dyn Progress,
+) -> Vec<u128> {
+    let mut ids = Vec::new();
+    // report in batches so the hook isn't called for every single id
+    let mut pending = 0;
+    for id in low..=high {
+        if rule.matches_in(id, radix) {
+            ids.push(id);
+        }
+        pending += 1;
+        if pending == PROGRESS_BATCH {
+            progress.advance(pending);
+            pending = 0;
+        }
+    }
+    progress.advance(pending);
+    ids
+}
+
+// The part of start..=end made of ids with `length` digits
+fn length_bounds(length: u32, start: u128, end: u128, radix: Radix) -> (u128, u128) {
+    // 0 is a 1 digit id like the others
+    let smallest = match length {
+        1 => 0,
+        _ => radix.pow(length - 1).expect("Length of an id that fits"),
+    };
+    let largest = radix.pow(length).map_or(u128::MAX, |power| power - 1);
+    (start.max(smallest), end.min(largest))
+}
+
+// Smallest and largest patterns of `period` digits, a repeated pattern is always shorter than u128
+fn pattern_bounds(period: u32, radix: Radix) -> (u128, u128) {
+    let power = radix.pow(period).expect("Pattern shorter than the id");
+    (power / radix.value() as u128, power - 1)
+}
+
+// Number of ids in low..=high, saturating for the whole of u128
+fn id_count(low: u128, high: u128) -> u128 {
+    if low > high {
+        0
+    } else {
+        (high - low).saturating_add(1)
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct InvalidTotals {
+    // Both can be well past u128 for ranges of long ids
+    pub count: BigUint,
+    pub sum: BigUint,
+}
+
+impl InvalidTotals {
+    pub fn combine(&self, other: &InvalidTotals) -> InvalidTotals {
+        InvalidTotals {
+            count: self.count.add(&other.count),
+            sum: self.sum.add(&other.sum),
+        }
+    }
+}
+
+// The ids pattern * multiplier for every pattern in first..=last, added or taken away
+struct SeriesTerm {
+    add: bool,
+    first: u128,
+    last: u128,
+    multiplier: u128,
+}
+
+// Inclusion–exclusion over the periods for ids of `length` digits: ids with periods p1, p2..
+// are exactly the ids with period gcd(p1, p2..), so those are taken away again (and added
+// back for three) to count each id once
+fn series_terms(
+    length: u32,
+    low: u128,
+    high: u128,
+    periods: &[u32],
+    radix: Radix,
+) -> Vec<SeriesTerm> {
+    let mut terms = Vec::new();
+    if low > high {
+        return terms;
+    }
+
+    for subset in 1..(1u32 << periods.len()) {
+        let chosen: Vec<u32> = (0..periods.len())
+            .filter(|i| subset & (1 << i) != 0)
+            .map(|i| periods[i])
+            .collect();
+        let period = chosen.iter().copied().fold(0, gcd);
+
+        let multiplier = radix.repeat_multiplier(period, length / period);
+        let first = low
+            .div_ceil(multiplier)
+            .max(pattern_bounds(period, radix).0);
+        let last = (high / multiplier).min(pattern_bounds(period, radix).1);
+        if first <= last {
+            terms.push(SeriesTerm {
+                add: chosen.len() % 2 == 1,
+                first,
+                last,
+                multiplier,
+            });
+        }
+    }
+    terms
+}
+
+// Fast path, None as soon as anything overflows
+fn totals_u128(terms: &[SeriesTerm]) -> Option<InvalidTotals> {
+    let (mut count_added, mut count_removed) = (0u128, 0u128);
+    let (mut sum_added, mut sum_removed) = (0u128, 0u128);
+    for term in terms {
+        let count = term.last - term.first + 1;
+        // first + last and count can't both be odd, so halve whichever is even
+        let ends = term.first.checked_add(term.last)?;
+        let pattern_sum = if count % 2 == 0 {
+            (count / 2).checked_mul(ends)?
+        } else {
+            count.checked_mul(ends / 2)?
+        };
+        let sum = pattern_sum.checked_mul(term.multiplier)?;
+        if term.add {
+            count_added = count_added.checked_add(count)?;
+            sum_added = sum_added.checked_add(sum)?;
+        } else {
+            count_removed = count_removed.checked_add(count)?;
+            sum_removed = sum_removed.checked_add(sum)?;
+        }
+    }
+    Some(InvalidTotals {
+        count: BigUint::from(count_added - count_removed),
+        sum: BigUint::from(sum_added - sum_removed),
+    })
+}
+
+// SeriesTerm for ids of any length
+struct WideTerm {
+    add: bool,
+    first: BigUint,
+    last: BigUint,
+    multiplier: BigUint,
+}
+
+impl From<&SeriesTerm> for WideTerm {
+    fn from(term: &SeriesTerm) -> Self {
+        WideTerm {
+            add: term.add,
+            first: BigUint::from(term.first),
+            last: BigUint::from(term.last),
+            multiplier: BigUint::from(term.multiplier),
+        }
+    }
+}
+
+// Same as series_terms with no limit on the length of the ids
+fn wide_series_terms(
+    length: u32,
+    low: &BigUint,
+    high: &BigUint,
+    periods: &[u32],
+    radix: Radix,
+) -> Vec<WideTerm> {
+    let mut terms = Vec::new();
+    if low > high {
+        return terms;
+    }
+
+    for subset in 1..(1u32 << periods.len()) {
+        let chosen: Vec<u32> = (0..periods.len())
+            .filter(|i| subset & (1 << i) != 0)
+            .map(|i| periods[i])
+            .collect();
+        let period = chosen.iter().copied().fold(0, gcd);
+
+        let shift = BigUint::pow(radix, period);
+        let multiplier = (1..length / period).fold(BigUint::from(1), |multiplier, _| {
+            multiplier.mul(&shift).add(&BigUint::from(1))
+        });
+        let smallest = BigUint::pow(radix, period - 1);
+        let largest = shift
+            .checked_sub(&BigUint::from(1))
+            .expect("Power is positive");
+        let first = low.div_ceil(&multiplier).max(smallest);
+        let last = high.div_rem(&multiplier).0.min(largest);
+        if first <= last {
+            terms.push(WideTerm {
+                add: chosen.len() % 2 == 1,
+                first,
+                last,
+                multiplier,
+            });
+        }
+    }
+    terms
+}
+
+fn totals_wide(terms: &[WideTerm]) -> InvalidTotals {
+    let (mut count_added, mut count_removed) = (BigUint::ZERO, BigUint::ZERO);
+    let (mut sum_added, mut sum_removed) = (BigUint::ZERO, BigUint::ZERO);
+    for term in terms {
+        let count = term
+            .last
+            .add(&BigUint::from(1))
+            .checked_sub(&term.first)
+            .expect("Terms are never empty");
+        // count * (first + last) is always even
+        let pattern_sum = count.mul(&term.first.add(&term.last)).div_rem_u64(2).0;
+        let sum = pattern_sum.mul(&term.multiplier);
+        if term.add {
+            count_added = count_added.add(&count);
+            sum_added = sum_added.add(&sum);
+        } else {
+            count_removed = count_removed.add(&count);
+            sum_removed = sum_removed.add(&sum);
+        }
+    }
+    InvalidTotals {
+        count: count_added
+            .checked_sub(&count_removed)
+            .expect("More ids removed than added"),
+        sum: sum_added
+            .checked_sub(&sum_removed)
+            .expect("More ids removed than added"),
+    }
+}
+
+// Invalid ids with `length` digits between low and high, sorted.
+// An id with several periods such as 111111 is made by more than one period, so
+// each id is only kept for the first period that makes it.
+fn generate_invalid_ids(
+    length: u32,
+    low: u128,
+    high: u128,
+    periods: &[u32],
+    radix: Radix,
+) -> Vec<u128> {
+    let mut ids = Vec::new();
+    if low > high {
+        return ids;
+    }
+
+    for (i, &period) in periods.iter().enumerate() {
+        let multiplier = radix.repeat_multiplier(period, length / period);
+        // every pattern of `period` digits gives an id of `length` digits
+        let first = low
+            .div_ceil(multiplier)
+            .max(pattern_bounds(period, radix).0);
+        let last = (high / multiplier).min(pattern_bounds(period, radix).1);
+
+        for pattern in first..=last {
+            let id = pattern * multiplier;
+            let seen = periods[..i]
+                .iter()
+                .any(|&earlier| radix.has_period(id, earlier));
+            if !seen {
+                ids.push(id);
+            }
+        }
+    }
+    ids.sort_unstable();
+    ids
+}
+
+// check if the first half of the string equals the second half
+pub fn has_repeated_digits(id: String) -> bool {
+    // split string in half
+    let len = id.len();
+    let half_len = len / 2;
+    let first_half = &id[0..half_len];
+    let second_half = &id[half_len..len];
+
+    //check for equality
+    first_half == second_half
+}
+
+pub fn has_repeated_advanced(id: String) -> bool {
+    let chars: Vec<char> = id.chars().collect();
+    let len = chars.len();
+
+    let mut str_fragment = String::new();
+
+    for i in 0..len - 1 {
+        str_fragment.push(chars[i]);
+        let fract = len / (i + 1);
+        // if fragment repeated fract times equals id
+        let repeated_fragment = str_fragment.repeat(fract);
+        if repeated_fragment == id {
+            return true;
+        }
+    }
+    false
+}
+
+// Number of ids checked between progress updates
+const PROGRESS_BATCH: u64 = 4096;
+
+// Ids shared by two of the input ranges, which would be counted twice without merging
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overlap {
+    // The earlier range that covers the shared ids and the one overlapping it
+    pub first: (u128, u128),
+    pub second: (u128, u128),
+    pub start: u128,
+    pub end: u128,
+    pub radix: Radix,
+}
+
+impl Overlap {
+    pub fn shared_ids(&self) -> IdRange {
+        IdRange::with_radix(self.start, self.end, self.radix)
+    }
+}
+
+// Merge overlapping and adjacent ranges, the overlaps found are returned as well.
+// Every id covered n times appears in n - 1 overlaps.
+pub fn merge_ranges(ranges: &[IdRange]) -> (Vec<IdRange>, Vec<Overlap>) {
+    // a range that ends before it starts has no ids to merge
+    let mut sorted: Vec<(u128, u128)> = ranges
+        .iter()
+        .filter(|r| r.start <= r.end)
+        .map(|r| (r.start, r.end))
+        .collect();
+    // Sort ranges by start value
+    sorted.sort();
+
+    let mut merged: Vec<IdRange> = Vec::new();
+    let mut overlaps: Vec<Overlap> = Vec::new();
+    let Some(&(first_start, first_end)) = sorted.first() else {
+        return (merged, overlaps);
+    };
+    // every range comes from the same input so shares its radix
+    let radix = ranges[0].radix;
+    let mut current = IdRange::with_radix(first_start, first_end, radix);
+    // the input range that reaches the end of the current merged range
+    let mut covering = (first_start, first_end);
+
+    for &(start, end) in sorted.iter().skip(1) {
+        if start <= current.end {
+            overlaps.push(Overlap {
+                first: covering,
+                second: (start, end),
+                start,
+                end: end.min(current.end),
+                radix,
+            });
+        }
+        if start <= current.end.saturating_add(1) {
+            // Ranges overlap or touch so merge them
+            if end > current.end {
+                current.end = end;
+                covering = (start, end);
+            }
+        } else {
+            // No overlap, add the current range to the list and start a new one
+            merged.push(current);
+            current = IdRange::with_radix(start, end, radix);
+            covering = (start, end);
+        }
+    }
+    merged.push(current);
+
+    (merged, overlaps)
+}
+
+// A range of ids too long for a u128, only the totals can be worked out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WideRange {
+    pub start: BigUint,
+    pub end: BigUint,
+    pub radix: Radix,
+}
+
+impl WideRange {
+    fn from_str(range_str: &str, radix: Radix) -> Result<Self, String> {
+        let (start, end) = range_str
+            .split_once('-')
+            .ok_or_else(|| format!("Invalid range {}", range_str))?;
+        Ok(WideRange {
+            start: BigUint::parse(start, radix)?,
+            end: BigUint::parse(end, radix)?,
+            radix,
+        })
+    }
+
+    // Errors for rules that have to check every id once the range is past u128
+    pub fn invalid_totals(&self, rule: &Rule) -> Result<InvalidTotals, String> {
+        if let (Some(start), Some(end)) = (self.start.to_u128(), self.end.to_u128()) {
+            return Ok(IdRange::with_radix(start, end, self.radix).invalid_totals(rule));
+        }
+
+        let mut terms = Vec::new();
+        for length in self.start.digit_count(self.radix)..=self.end.digit_count(self.radix) {
+            let periods = rule.periods(length).ok_or_else(|| {
+                format!(
+                    "Rule {} checks every id, {}-{} is too wide for that",
+                    rule, self.start, self.end
+                )
+            })?;
+            let smallest = match length {
+                1 => BigUint::ZERO,
+                _ => BigUint::pow(self.radix, length - 1),
+            };
+            let largest = BigUint::pow(self.radix, length)
+                .checked_sub(&BigUint::from(1))
+                .expect("Power is positive");
+            let low = self.start.clone().max(smallest);
+            let high = self.end.clone().min(largest);
+            terms.extend(wide_series_terms(length, &low, &high, &periods, self.radix));
+        }
+        Ok(totals_wide(&terms))
+    }
+}
+
+// Ranges whose ids can have any number of digits
+pub fn parse_wide_ranges(input: &str, radix: Radix) -> Result<Vec<WideRange>, String> {
+    input
+        .trim()
+        .split(',')
+        .map(|range| WideRange::from_str(range, radix))
+        .collect()
+}
+
+// Same as merge_ranges for wide ranges, without reporting the overlaps
+pub fn merge_wide_ranges(ranges: &[WideRange]) -> Vec<WideRange> {
+    // a range that ends before it starts has no ids to merge
+    let mut sorted: Vec<&WideRange> = ranges.iter().filter(|r| r.start <= r.end).collect();
+    sorted.sort_by(|a, b| a.start.cmp(&b.start));
+
+    let mut merged: Vec<WideRange> = Vec::new();
+    for range in sorted {
+        match merged.last_mut() {
+            // overlapping or touching
+            Some(current) if range.start <= current.end.add(&BigUint::from(1)) => {
+                if range.end > current.end {
+                    current.end = range.end.clone();
+                }
+            }
+            _ => merged.push(range.clone()),
+        }
+    }
+    merged
+}
+
+// Sum of the invalid ids already found, with the policy deciding what happens past u128
+pub fn sum_invalid_ids(ranges: &[IdRange], policy: OverflowPolicy) -> Result<BigUint, String> {
+    let mut total = BigUint::ZERO;
+    // add up in a u128 and only move into the wide total when that would overflow
+    let mut partial: u128 = 0;
+    for &id in ranges.iter().flat_map(|range| range.invalid_ids.iter()) {
+        partial = match partial.checked_add(id) {
+            Some(sum) => sum,
+            None => {
+                total = total.add(&BigUint::from(partial));
+                id
+            }
+        };
+    }
+    policy.apply(total.add(&BigUint::from(partial)))
+}
+
+// Whether ranges are merged before looking for invalid ids
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlapMode {
+    Merge,
+    // Each range on its own, ids in more than one range count more than once
+    KeepOverlapping,
+}
+
+// Sum of every invalid id across the comma separated ranges, using the part 2 rule
+pub fn solve(input: &str) -> u128 {
+    solve_with_rule(input, &Rule::AtLeastTwice, OverlapMode::Merge)
+}
+
+pub fn solve_with_rule(input: &str, rule: &Rule, mode: OverlapMode) -> u128 {
+    let mut ranges = parse_ranges(input);
+    if mode == OverlapMode::Merge {
+        ranges = merge_ranges(&ranges).0;
+    }
+    for range in ranges.iter_mut() {
+        range.find_invalid_ids(rule, &mut NoProgress);
+    }
+    let sum = sum_invalid_ids(&ranges, OverflowPolicy::Error)
+        .unwrap_or_else(|e| panic!("Sum of invalid IDs overflowed: {}", e));
+    sum.to_u128().expect("Checked by the overflow policy")
+}
+
+// split input on commas
+pub fn parse_ranges(input: &str) -> Vec<IdRange> {
+    parse_ranges_in(input, Radix::DECIMAL)
+}
+
+// Ranges written in another radix, e.g. "ABA0-ABBF" in hex
+pub fn parse_ranges_in(input: &str, radix: Radix) -> Vec<IdRange> {
+    input
+        .trim()
+        .split(',')
+        .map(|range| IdRange::from_str(range, radix))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_repeated_digits() {
+        assert_eq!(has_repeated_digits("1212".to_string()), true);
+        assert_eq!(has_repeated_digits("1234".to_string()), false);
+        assert_eq!(has_repeated_digits("1111".to_string()), true);
+        assert_eq!(has_repeated_digits("123123".to_string()), true);
+    }
+
+    #[test]
+    fn test_is_invalid() {
+        assert!(Rule::AtLeastTwice.matches(1212));
+        assert!(!Rule::AtLeastTwice.matches(1234));
+        assert!(Rule::AtLeastTwice.matches(1111));
+        assert!(!Rule::AtLeastTwice.matches(12345));
+    }
+
+    #[test]
+    fn test_from_str() {
+        let id_range = IdRange::from_str("100-200", Radix::DECIMAL);
+        assert_eq!(id_range.start, 100);
+        assert_eq!(id_range.end, 200);
+    }
+
+    #[test]
+    fn test_reversed_range() {
+        let mut id_range = IdRange::from_str("200-100", Radix::DECIMAL);
+        assert_eq!(id_range.number_ids_in_range(), 0);
+        id_range.find_invalid_ids(&Rule::AtLeastTwice, &mut NoProgress);
+        assert!(id_range.invalid_ids.is_empty());
+        assert_eq!(IdRange::new(0, u128::MAX).number_ids_in_range(), u128::MAX);
+    }
+
+    #[test]
+    fn test_find_invalid_ids() {
+        let mut id_range = IdRange::new(95, 115);
+        id_range.find_invalid_ids(&Rule::Twice, &mut NoProgress);
+        assert_eq!(id_range.invalid_ids, vec![99]);
+
+        let mut id_range = IdRange::new(95, 115);
+        id_range.find_invalid_ids(&Rule::AtLeastTwice, &mut NoProgress);
+        assert_eq!(id_range.invalid_ids, vec![99, 111]);
+    }
+
+    fn scan(start: u128, end: u128, rule: &Rule) -> Vec<u128> {
+        scan_ids(start, end, rule, Radix::DECIMAL, &mut NoProgress)
+    }
+
+    fn generate(start: u128, end: u128, rule: &Rule) -> Vec<u128> {
+        generate_in(start, end, rule, Radix::DECIMAL)
+    }
+
+    fn generate_in(start: u128, end: u128, rule: &Rule, radix: Radix) -> Vec<u128> {
+        (radix.digit_count(start)..=radix.digit_count(end))
+            .flat_map(|length| {
+                let (low, high) = length_bounds(length, start, end, radix);
+                let periods = rule.periods(length).unwrap();
+                generate_invalid_ids(length, low, high, &periods, radix)
+            })
+            .collect()
+    }
+
+    // Every rule that can be generated, Palindrome and And can't
+    fn generated_rules() -> Vec<Rule> {
+        vec![
+            Rule::Twice,
+            Rule::AtLeastTwice,
+            Rule::Exactly(3),
+            Rule::MinPatternLength(2),
+            Rule::Or(vec![Rule::Exactly(3), Rule::Twice]),
+        ]
+    }
+
+    #[test]
+    fn test_generate_matches_scan() {
+        let ranges = [
+            (0, 2000),
+            (95, 115),
+            (998, 1012),
+            (1188511880, 1188511890),
+            (222220, 222224),
+            (1698522, 1698528),
+            (446443, 446449),
+            (38593856, 38593862),
+            (565653, 565659),
+            (824824821, 824824827),
+            (2121212118, 2121212124),
+            (99990, 1000020),
+        ];
+        for (start, end) in ranges {
+            for rule in generated_rules() {
+                assert_eq!(
+                    generate(start, end, &rule),
+                    scan(start, end, &rule),
+                    "{}-{} rule {}",
+                    start,
+                    end,
+                    rule
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_multiple_periods() {
+        // 111111 repeats with periods 1, 2 and 3 but is only listed once
+        for rule in generated_rules() {
+            assert_eq!(generate(111110, 111112, &rule), vec![111111]);
+        }
+        // 12 digit ids have repeat counts 2 and 3 and both overlap at 6 repeats of a pair
+        let ids = generate(121212121200, 121212121299, &Rule::AtLeastTwice);
+        assert_eq!(ids, vec![121212121212]);
+    }
+
+    #[test]
+    fn test_generate_large_ranges() {
+        // the longest ids u128 can hold, 38 and 39 digits
+        let repeated = 1234567890123456789u128 * (10u128.pow(19) + 1);
+        for (start, end) in [
+            (repeated - 500, repeated + 500),
+            (u128::MAX - 1000, u128::MAX),
+            (10u128.pow(38) - 500, 10u128.pow(38) + 500),
+        ] {
+            for rule in generated_rules() {
+                assert_eq!(generate(start, end, &rule), scan(start, end, &rule));
+            }
+        }
+        assert_eq!(
+            generate(repeated - 5, repeated + 5, &Rule::Twice),
+            vec![repeated]
+        );
+
+        let ids = generate(1, 10u128.pow(12), &Rule::Twice);
+        // 9 + 90 + 900 + ... for each even length up to 12
+        assert_eq!(ids.len(), 999999);
+        assert_eq!(*ids.last().unwrap(), 999999999999);
+    }
+
+    #[test]
+    fn test_generate_other_radixes() {
+        for radix in [2, 3, 16, 36] {
+            let radix = Radix::new(radix).unwrap();
+            let max = u128::MAX;
+            for (start, end) in [(0, 5000), (max - 2000, max)] {
+                for rule in generated_rules() {
+                    assert_eq!(
+                        generate_in(start, end, &rule, radix),
+                        scan_ids(start, end, &rule, radix, &mut NoProgress),
+                        "{}-{} radix {} rule {}",
+                        start,
+                        end,
+                        radix.value(),
+                        rule
+                    );
+                }
+            }
+        }
+
+        let hex = Radix::new(16).unwrap();
+        let mut range = parse_ranges_in("ABAA-ABAC", hex).remove(0);
+        range.find_invalid_ids(&Rule::Twice, &mut NoProgress);
+        assert_eq!(range.invalid_ids, vec![0xABAB]);
+        let totals = range.invalid_totals(&Rule::Twice);
+        assert_eq!(
+            (totals.count.to_u128(), totals.sum.to_u128()),
+            (Some(1), Some(0xABAB))
+        );
+    }
+
+    #[test]
+    fn test_invalid_totals_match_ids() {
+        let ranges = [(0, 2000), (95, 115), (99990, 1000020), (1, 10u128.pow(7))];
+        for (start, end) in ranges {
+            for rule in generated_rules() {
+                let ids = generate(start, end, &rule);
+                let totals = IdRange::new(start, end).invalid_totals(&rule);
+                assert_eq!(totals.count.to_u128(), Some(ids.len() as u128));
+                assert_eq!(totals.sum.to_u128(), Some(ids.iter().sum::<u128>()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_totals_inclusion_exclusion() {
+        // 30 digits has repeat counts 2, 3 and 5, which all overlap at 30 repeats
+        let start = 10u128.pow(29);
+        let ones = (10u128.pow(30) - 1) / 9;
+        let totals = IdRange::new(ones - 10, ones + 10).invalid_totals(&Rule::AtLeastTwice);
+        assert_eq!(totals.count.to_u128(), Some(1));
+        assert_eq!(totals.sum.to_u128(), Some(ones));
+
+        // periods 15, 10 and 6 overlap pairwise at periods 5, 3 and 2, and all at period 1,
+        // with 9 * 10^(p - 1) patterns of each period p
+        let totals = IdRange::new(start, 10u128.pow(30) - 1).invalid_totals(&Rule::AtLeastTwice);
+        assert_eq!(
+            totals.count.to_u128().unwrap(),
+            9 * 10u128.pow(14) + 9 * 10u128.pow(9) + 9 * 10u128.pow(5)
+                - 9 * 10u128.pow(4)
+                - 9 * 10u128.pow(2)
+                - 9 * 10
+                + 9
+        );
+    }
+
+    #[test]
+    fn test_invalid_totals_wide() {
+        // every 38 digit id that is a 19 digit pattern twice, the sum needs more than u128
+        let totals = IdRange::new(10u128.pow(37), 10u128.pow(38) - 1).invalid_totals(&Rule::Twice);
+        assert_eq!(totals.count.to_u128(), Some(9 * 10u128.pow(18)));
+        assert_eq!(totals.sum.to_u128(), None);
+        assert_eq!(
+            totals.sum.to_string(),
+            "495000000000000000004499999999999999995500000000000000000"
+        );
+
+        let combined = totals.combine(&IdRange::new(11, 11).invalid_totals(&Rule::Twice));
+        assert_eq!(combined.count.to_u128(), Some(9 * 10u128.pow(18) + 1));
+        assert_eq!(
+            combined.sum.to_string(),
+            "495000000000000000004499999999999999995500000000000000011"
+        );
+    }
+
+    #[test]
+    fn test_wide_ranges() {
+        // ranges that fit in a u128 give the same totals as IdRange
+        let ranges = parse_wide_ranges("95-115,99990-1000020", Radix::DECIMAL).unwrap();
+        for (wide, (start, end)) in ranges.iter().zip([(95, 115), (99990, 1000020)]) {
+            for rule in generated_rules() {
+                assert_eq!(
+                    wide.invalid_totals(&rule),
+                    Ok(IdRange::new(start, end).invalid_totals(&rule))
+                );
+            }
+        }
+
+        // a 20 digit pattern twice is a 40 digit id, past 10^39
+        let pattern = "12345678901234567890";
+        let repeated = format!("{}{}", pattern, pattern);
+        let range = format!("{}0-{}9", &repeated[..39], &repeated[..39]);
+        let wide = parse_wide_ranges(&range, Radix::DECIMAL).unwrap();
+        let totals = wide[0].invalid_totals(&Rule::Twice).unwrap();
+        assert_eq!(totals.count.to_u128(), Some(1));
+        assert_eq!(totals.sum.to_string(), repeated);
+
+        // the same id in two overlapping ranges is only counted once after merging
+        let overlapping = format!("{},{}0-{}5", range, &repeated[..39], &repeated[..39]);
+        let wide = parse_wide_ranges(&overlapping, Radix::DECIMAL).unwrap();
+        // both ranges hold the one id
+        let twice: Vec<InvalidTotals> = wide
+            .iter()
+            .map(|range| range.invalid_totals(&Rule::Twice).unwrap())
+            .collect();
+        assert_eq!(twice[0], twice[1]);
+        let merged = merge_wide_ranges(&wide);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].start, wide[0].start);
+        assert_eq!(merged[0].end, wide[0].end);
+        let totals = merged[0].invalid_totals(&Rule::Twice).unwrap();
+        assert_eq!(totals.count.to_u128(), Some(1));
+        assert_eq!(
+            merge_wide_ranges(&parse_wide_ranges("1-5,9-12,6-7,30-20", Radix::DECIMAL).unwrap()),
+            parse_wide_ranges("1-7,9-12", Radix::DECIMAL).unwrap()
+        );
+
+        // every id with 39 to 41 digits, only the 40 digit ones are a pattern twice
+        let range = format!("1{}-{}", "0".repeat(38), "9".repeat(41));
+        let wide = parse_wide_ranges(&range, Radix::DECIMAL).unwrap();
+        let totals = wide[0].invalid_totals(&Rule::Twice).unwrap();
+        assert_eq!(totals.count.to_string(), format!("9{}", "0".repeat(19)));
+        // 9 * 10^19 patterns averaging 55 * 10^18 - 1/2, each times 10^20 + 1
+        let expected = BigUint::from(9 * 10u128.pow(19))
+            .mul(&BigUint::from(11 * 10u128.pow(19) - 1))
+            .div_rem_u64(2)
+            .0
+            .mul(&BigUint::pow(Radix::DECIMAL, 20).add(&BigUint::from(1)));
+        assert_eq!(totals.sum, expected);
+
+        // ids past u128 can't be checked one at a time
+        assert!(wide[0].invalid_totals(&Rule::Palindrome).is_err());
+        assert!(parse_wide_ranges("1-2,x", Radix::DECIMAL).is_err());
+    }
+
+    #[test]
+    fn test_sum_overflow_policy() {
+        // 13 repeats of a 3 digit pattern are 39 digit ids, a few hundred of them overflow u128
+        let mut range = IdRange::new(10u128.pow(38), u128::MAX);
+        range.find_invalid_ids(&Rule::Exactly(13), &mut NoProgress);
+        let ranges = [range];
+        let totals = ranges[0].invalid_totals(&Rule::Exactly(13));
+        assert_eq!(
+            totals.count.to_u128(),
+            Some(ranges[0].invalid_ids.len() as u128)
+        );
+        assert_eq!(totals.sum.to_u128(), None);
+
+        assert!(sum_invalid_ids(&ranges, OverflowPolicy::Error).is_err());
+        assert_eq!(
+            sum_invalid_ids(&ranges, OverflowPolicy::Saturate),
+            Ok(BigUint::from(u128::MAX))
+        );
+        assert_eq!(
+            sum_invalid_ids(&ranges, OverflowPolicy::Widen),
+            Ok(totals.sum)
+        );
+    }
+
+    #[test]
+    fn test_has_repeated_advanced() {
+        assert_eq!(has_repeated_advanced("1212".to_string()), true);
+        assert_eq!(has_repeated_advanced("123123".to_string()), true);
+        assert_eq!(has_repeated_advanced("111".to_string()), true);
+        assert_eq!(has_repeated_advanced("1234".to_string()), false);
+        assert_eq!(has_repeated_advanced("12341234".to_string()), true);
+         assert_eq!(has_repeated_advanced("123123123123123".to_string()), true);
+    }
+
+    #[test]
+    fn test_find_matches_scan() {
+        for (start, end) in [(1, 5000), (95, 115), (565653, 565659)] {
+            let mut found = IdRange::new(start, end);
+            let mut scanned = IdRange::new(start, end);
+            found.find_invalid_ids(&Rule::AtLeastTwice, &mut NoProgress);
+            scanned.scan_invalid_ids(&Rule::AtLeastTwice, &mut NoProgress);
+            assert_eq!(found.invalid_ids, scanned.invalid_ids);
+        }
+    }
+
+    #[test]
+    fn test_rules_that_are_checked() {
+        // palindromes can't be generated so each length falls back to checking ids
+        let palindrome_or_twice = Rule::parse("palindrome|twice").unwrap();
+        let mut range = IdRange::new(95, 1012);
+        range.find_invalid_ids(&palindrome_or_twice, &mut NoProgress);
+        assert_eq!(range.invalid_ids[..4], [99, 101, 111, 121]);
+
+        // 0 is a palindrome too
+        for (start, end) in [(95, 1012), (0, 20)] {
+            for rule in [&palindrome_or_twice, &Rule::Palindrome] {
+                let mut range = IdRange::new(start, end);
+                range.find_invalid_ids(rule, &mut NoProgress);
+                assert_eq!(range.invalid_ids, scan(start, end, rule));
+                let ids: Vec<u128> = range.invalid_ids(rule).collect();
+                assert_eq!(ids, range.invalid_ids);
+
+                let totals = range.invalid_totals(rule);
+                assert_eq!(
+                    totals.count.to_u128(),
+                    Some(range.invalid_ids.len() as u128)
+                );
+                assert_eq!(
+                    totals.sum.to_u128(),
+                    Some(range.invalid_ids.iter().sum::<u128>())
+                );
+            }
+        }
+        let mut range = IdRange::new(0, 20);
+        range.find_invalid_ids(&Rule::Palindrome, &mut NoProgress);
+        assert_eq!(range.invalid_ids, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 11]);
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve("95-115,998-1012\n"), 99 + 111 + 999 + 1010);
+        assert_eq!(
+            solve_with_rule("95-115,998-1012\n", &Rule::Twice, OverlapMode::Merge),
+            99 + 1010
+        );
+    }
+
+    #[test]
+    fn test_merge_ranges() {
+        let ranges = parse_ranges("17-30,1-10,5-15,8-12,16-16,40-50");
+        let (merged, overlaps) = merge_ranges(&ranges);
+        let merged: Vec<(u128, u128)> = merged.iter().map(|r| (r.start, r.end)).collect();
+        assert_eq!(merged, vec![(1, 30), (40, 50)]);
+
+        assert_eq!(overlaps.len(), 2);
+        assert_eq!(overlaps[0].first, (1, 10));
+        assert_eq!(overlaps[0].second, (5, 15));
+        assert_eq!((overlaps[0].start, overlaps[0].end), (5, 10));
+        assert_eq!(overlaps[1].first, (5, 15));
+        assert_eq!((overlaps[1].start, overlaps[1].end), (8, 12));
+        // 5-7 and 11-12 are covered twice and 8-10 three times
+        let extra: u128 = overlaps
+            .iter()
+            .map(|o| o.shared_ids().number_ids_in_range())
+            .sum();
+        assert_eq!(extra, 3 + 2 + 2 * 3);
+
+        // reversed ranges are dropped rather than overlapping anything
+        let (merged, overlaps) = merge_ranges(&parse_ranges("1-10,5-3,20-15,16-30"));
+        let merged: Vec<(u128, u128)> = merged.iter().map(|r| (r.start, r.end)).collect();
+        assert_eq!(merged, vec![(1, 10), (16, 30)]);
+        assert!(overlaps.is_empty());
+
+        assert_eq!(merge_ranges(&[]).0.len(), 0);
+        let (merged, _) = merge_ranges(&[IdRange::new(5, u128::MAX), IdRange::new(1, 4)]);
+        assert_eq!((merged[0].start, merged[0].end), (1, u128::MAX));
+    }
+
+    #[test]
+    fn test_solve_overlapping() {
+        let input = "95-115,110-120,998-1012";
+        assert_eq!(solve(input), 99 + 111 + 999 + 1010);
+        assert_eq!(
+            solve_with_rule(input, &Rule::AtLeastTwice, OverlapMode::KeepOverlapping),
+            99 + 111 + 111 + 999 + 1010
+        );
+    }
+}
@@ -1,170 +1,136 @@
-struct IdRange {
-    start: u128,
-    end: u128,
-    invalid_ids: Vec<u128>,
-}
+use aoc_common::args::Args;
+use aoc_common::progress::ProgressBar;
+use day_2::parallel::find_invalid_ids_parallel;
+use day_2::period::Radix;
+use day_2::report::RangeReport;
+use day_2::rule::Rule;
+use day_2::wide::OverflowPolicy;
+use day_2::{
+    InvalidTotals, merge_ranges, merge_wide_ranges, parse_ranges_in, parse_wide_ranges,
+    sum_invalid_ids,
+};
+
+fn main() {
+    let args = Args::from_env();
+    let input_path = "./input.txt";
+    let input = std::fs::read_to_string(input_path).expect("Failed to read input file");
 
-impl IdRange {
-    fn new(start: u128, end: u128) -> Self {
-        IdRange {
-            start,
-            end,
-            invalid_ids: Vec::new(),
+    // e.g. --radix 16 for hex ids, the sums are still printed in decimal
+    let radix = match args.parsed::<u32>("--radix") {
+        Some(radix) => Radix::new(radix).unwrap_or_else(|e| panic!("{}", e)),
+        None => Radix::DECIMAL,
+    };
+
+    // e.g. --rule "twice|exactly:3", defaults to the part 2 rule
+    let rule = match args.value("--rule") {
+        Some(text) => Rule::parse(text).unwrap_or_else(|e| panic!("{}", e)),
+        None => Rule::AtLeastTwice,
+    };
+
+    // what to do when a sum doesn't fit in a u128: error (default), saturate or widen
+    let policy = match args.value("--overflow") {
+        Some(text) => OverflowPolicy::parse(text).unwrap_or_else(|e| panic!("{}", e)),
+        None => OverflowPolicy::Error,
+    };
+
+    // --wide allows ids longer than u128, only the totals are worked out
+    if args.flag("--wide") {
+        let mut ranges = parse_wide_ranges(&input, radix).unwrap_or_else(|e| panic!("{}", e));
+        // merged the same way as below unless --overlapping is given
+        if !args.flag("--overlapping") {
+            let merged = merge_wide_ranges(&ranges);
+            if merged.len() < ranges.len() {
+                println!("Merged {} ranges into {}", ranges.len(), merged.len());
+            }
+            ranges = merged;
         }
+        let mut totals = InvalidTotals::default();
+        for range in &ranges {
+            let range_totals = range
+                .invalid_totals(&rule)
+                .unwrap_or_else(|e| panic!("{}", e));
+            totals = totals.combine(&range_totals);
+        }
+        let sum = policy.apply(totals.sum).unwrap_or_else(|e| panic!("{}", e));
+        println!("Invalid IDs: {}, sum {}", totals.count, sum);
+        return;
     }
 
-    fn from_str(range_str: &str) -> Self {
-        let parts: Vec<&str> = range_str.split('-').collect();
-        let start = parts[0].parse::<u128>();
-
-        let range_start = match start {
-            Err(range_start) => panic!("Invalid start range: {}", range_start),
-            Ok(range_start) => range_start,
-        };
-
-        let end = parts[1].parse::<u128>();
-
-        let range_end = match end {
-            Err(range_end) => panic!("Invalid end range: {}", range_end),
-            Ok(range_end) => range_end,
-        };
-
-        IdRange::new(range_start, range_end)
+    let mut ranges = parse_ranges_in(&input, radix);
+
+    // --overlapping keeps the old behaviour of counting ids in several ranges more than once
+    if !args.flag("--overlapping") {
+        let (merged, overlaps) = merge_ranges(&ranges);
+        for overlap in &overlaps {
+            let duplicates = overlap.shared_ids().invalid_totals(&rule);
+            println!(
+                "Ranges {}-{} and {}-{} share {}-{}, {} invalid IDs summing to {}",
+                radix.format(overlap.first.0),
+                radix.format(overlap.first.1),
+                radix.format(overlap.second.0),
+                radix.format(overlap.second.1),
+                radix.format(overlap.start),
+                radix.format(overlap.end),
+                duplicates.count,
+                duplicates.sum
+            );
+        }
+        if merged.len() < ranges.len() {
+            println!("Merged {} ranges into {}", ranges.len(), merged.len());
+        }
+        ranges = merged;
     }
 
-    fn is_invalid_simple(&self, id: u128) -> bool {
-        let string_id = id.to_string();
-        // check length of id
-        let string_len = string_id.len();
-
-        if string_len % 2 == 0 {
-            return has_repeated_digits(string_id);
-        } else {
-            return false;
+    // --nth 5 streams through the ranges in order and stops at the 5th invalid id
+    if let Some(n) = args.parsed::<usize>("--nth") {
+        let mut ids = ranges.iter().flat_map(|range| range.invalid_ids(&rule));
+        match n.checked_sub(1).and_then(|skip| ids.nth(skip)) {
+            Some(id) => println!("Invalid ID {}: {}", n, radix.format(id)),
+            None => println!("There are fewer than {} invalid IDs", n),
         }
+        return;
     }
 
-    fn is_invalid_advanced(&self, id: u128) -> bool {
-        let string_id = id.to_string();
-        
-        has_repeated_advanced(string_id)
+    // count and sum straight from the ranges without listing the ids
+    if args.flag("--totals") {
+        let totals = ranges
+            .iter()
+            .map(|range| range.invalid_totals(&rule))
+            .fold(InvalidTotals::default(), |acc, totals| acc.combine(&totals));
+        let sum = policy.apply(totals.sum).unwrap_or_else(|e| panic!("{}", e));
+        println!("Invalid IDs: {}, sum {}", totals.count, sum);
+        return;
     }
 
-    fn is_invalid(&self, id: u128) -> bool {
-        if USE_ADVANCED {
-            self.is_invalid_advanced(id)
-        } else {
-            self.is_invalid_simple(id)
+    let total_ids = ranges.iter().fold(0u128, |total, range| {
+        total.saturating_add(range.number_ids_in_range())
+    });
+    let mut progress = ProgressBar::new(
+        "Scanning IDs",
+        Some(u64::try_from(total_ids).unwrap_or(u64::MAX)),
+    );
+
+    // --threads 1 for one range at a time, the results are the same for any number of threads
+    let threads = args
+        .parsed::<usize>("--threads")
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |threads| threads.get()));
+    find_invalid_ids_parallel(&mut ranges, &rule, threads, &mut progress);
+
+    // why each id was picked, checked against the selected rule and the two puzzle rules
+    if args.flag("--report") {
+        let mut audited = vec![Rule::Twice, Rule::AtLeastTwice];
+        if !audited.contains(&rule) {
+            audited.push(rule.clone());
         }
-    }
-
-    fn find_invalid_ids(&mut self) {
-        for id in self.start..=self.end {
-            if self.is_invalid(id) {
-                self.invalid_ids.push(id);
+        for range in &ranges {
+            let report = RangeReport::new(range, &audited);
+            println!("{}", report.summary());
+            for id in &report.ids {
+                println!("  {}", id.line());
             }
         }
     }
-}
-
-// check if the first half of the string equals the second half
-fn has_repeated_digits(id: String) -> bool {
-    // split string in half
-    let len = id.len();
-    let half_len = len / 2;
-    let first_half = &id[0..half_len];
-    let second_half = &id[half_len..len];
 
-    //check for equality
-    first_half == second_half
-}
-
-fn has_repeated_advanced(id: String) -> bool {
-    let chars: Vec<char> = id.chars().collect();
-    let len = chars.len();
-
-    let mut str_fragment = String::new();
-
-    for i in 0..len - 1 {
-        str_fragment.push(chars[i]);
-        let fract = len / (i + 1);
-        // if fragment repeated fract times equals id
-        let repeated_fragment = str_fragment.repeat(fract);
-        if repeated_fragment == id {
-            return true;
-        }
-    }
-    false
-}
-
-// Toggle advanced mode for part 2
-const USE_ADVANCED: bool = true;
-
-fn main() {
-    let input_path = "./input.txt";
-    let input = std::fs::read_to_string(input_path).expect("Failed to read input file");
-
-    // split input on commas
-    let parts: Vec<&str> = input.trim().split(',').collect();
-    let mut ranges = Vec::<IdRange>::new();
-    let mut invalid_ids: Vec<u128> = Vec::new();
-
-    for part in parts {
-        println!("Processing range: {}", part);
-        let mut range = IdRange::from_str(part);
-        range.find_invalid_ids();
-        invalid_ids.extend(range.invalid_ids.clone());
-
-        ranges.push(range);
-    }
-
-    //println!("Invalid IDs found: {:?}", invalid_ids);
-
-    let invalid_id_sum = invalid_ids.iter().sum::<u128>();
+    let invalid_id_sum = sum_invalid_ids(&ranges, policy).unwrap_or_else(|e| panic!("{}", e));
     println!("Sum of invalid IDs: {}", invalid_id_sum);
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn test_has_repeated_digits() {
-        assert_eq!(has_repeated_digits("1212".to_string()), true);
-        assert_eq!(has_repeated_digits("1234".to_string()), false);
-        assert_eq!(has_repeated_digits("1111".to_string()), true);
-        assert_eq!(has_repeated_digits("123123".to_string()), true);
-    }
-
-    #[test]
-    fn test_is_invalid() {
-        let id_range = IdRange::new(1000, 2000);
-        assert_eq!(id_range.is_invalid(1212), true);
-        assert_eq!(id_range.is_invalid(1234), false);
-        assert_eq!(id_range.is_invalid(1111), true);
-        assert_eq!(id_range.is_invalid(12345), false);
-    }
-
-    #[test]
-    fn test_from_str() {
-        let id_range = IdRange::from_str("100-200");
-        assert_eq!(id_range.start, 100);
-        assert_eq!(id_range.end, 200);
-    }
-
-    #[test]
-    fn test_find_invalid_ids() {
-        let mut id_range = IdRange::new(95, 115);
-        id_range.find_invalid_ids();
-        assert_eq!(id_range.invalid_ids, vec![99]);
-    }
-
-    #[test]
-    fn test_has_repeated_advanced() {
-        assert_eq!(has_repeated_advanced("1212".to_string()), true);
-        assert_eq!(has_repeated_advanced("123123".to_string()), true);
-        assert_eq!(has_repeated_advanced("111".to_string()), true);
-        assert_eq!(has_repeated_advanced("1234".to_string()), false);
-        assert_eq!(has_repeated_advanced("12341234".to_string()), true);
-         assert_eq!(has_repeated_advanced("123123123123123".to_string()), true);
-    }
-}
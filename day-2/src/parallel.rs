@@ -0,0 +1,187 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::sync::mpsc;
+use std::thread;
+
+use aoc_common::progress::{NoProgress, Progress};
+
+use crate::IdRange;
+use crate::period::Radix;
+use crate::rule::Rule;
+
+// Ranges are never split into pieces smaller than this many ids
+const MIN_CHUNK: u128 = 1 << 16;
+// Pieces per thread, enough to steal from when some are slower than others
+const CHUNKS_PER_THREAD: u128 = 8;
+
+// Part of one of the input ranges, handled by a single thread
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    // Index of the range it came from
+    pub range: usize,
+    pub start: u128,
+    pub end: u128,
+    pub radix: Radix,
+}
+
+// Cut every range into chunks of at most chunk_size ids, in the same order as the ranges
+pub fn split_ranges(ranges: &[IdRange], chunk_size: u128) -> Vec<Chunk> {
+    let mut chunks = Vec::new();
+    for (index, range) in ranges.iter().enumerate() {
+        let mut start = range.start;
+        while start <= range.end {
+            let end = start.saturating_add(chunk_size - 1).min(range.end);
+            chunks.push(Chunk {
+                range: index,
+                start,
+                end,
+                radix: range.radix,
+            });
+            if end == range.end {
+                break;
+            }
+            start = end + 1;
+        }
+    }
+    chunks
+}
+
+// Same as find_invalid_ids on every range, spread over `threads` threads. The ids end up
+// in the same order whatever the number of threads, since each chunk's ids go back in its place.
+// Finishes the progress once every range is done.
+pub fn find_invalid_ids_parallel(
+    ranges: &mut [IdRange],
+    rule: &Rule,
+    threads: usize,
+    progress: &mut dyn Progress,
+) {
+    let threads = threads.max(1);
+    let total: u128 = ranges.iter().fold(0, |total, range| {
+        total.saturating_add(range.number_ids_in_range())
+    });
+    let chunk_size = (total / (threads as u128 * CHUNKS_PER_THREAD)).max(MIN_CHUNK);
+    find_in_chunks(ranges, rule, threads, chunk_size, progress);
+    progress.finish();
+}
+
+fn find_in_chunks(
+    ranges: &mut [IdRange],
+    rule: &Rule,
+    threads: usize,
+    chunk_size: u128,
+    progress: &mut dyn Progress,
+) {
+    let chunks = split_ranges(ranges, chunk_size);
+    // every thread starts with every nth chunk and steals from the back of the others once
+    // it runs out, nothing is added later so all queues empty means all work is taken
+    let queues: Vec<Mutex<VecDeque<usize>>> = (0..threads)
+        .map(|worker| Mutex::new((worker..chunks.len()).step_by(threads).collect()))
+        .collect();
+    let mut found: Vec<Vec<u128>> = vec![Vec::new(); chunks.len()];
+
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+        for worker in 0..threads {
+            let sender = sender.clone();
+            let (queues, chunks) = (&queues, &chunks);
+            scope.spawn(move || {
+                while let Some(index) = take_chunk(queues, worker) {
+                    let chunk = &chunks[index];
+                    let mut range = IdRange::with_radix(chunk.start, chunk.end, chunk.radix);
+                    range.find_invalid_ids(rule, &mut NoProgress);
+                    let checked = (chunk.end - chunk.start).saturating_add(1);
+                    sender
+                        .send((index, range.invalid_ids, checked))
+                        .expect("Receiver outlives the workers");
+                }
+            });
+        }
+        drop(sender);
+
+        // progress is only touched from this thread
+        for (index, ids, checked) in receiver {
+            progress.advance(checked.try_into().unwrap_or(u64::MAX));
+            found[index] = ids;
+        }
+    });
+
+    for (chunk, ids) in chunks.iter().zip(found) {
+        ranges[chunk.range].invalid_ids.extend(ids);
+    }
+}
+
+// Own queue first, then steal from the others
+fn take_chunk(queues: &[Mutex<VecDeque<usize>>], worker: usize) -> Option<usize> {
+    let own = queues[worker].lock().expect("Worker panicked").pop_front();
+    own.or_else(|| {
+        (1..queues.len()).find_map(|offset| {
+            let victim = (worker + offset) % queues.len();
+            queues[victim].lock().expect("Worker panicked").pop_back()
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_ranges;
+
+    fn sequential(input: &str, rule: &Rule) -> Vec<Vec<u128>> {
+        let mut ranges = parse_ranges(input);
+        for range in ranges.iter_mut() {
+            range.find_invalid_ids(rule, &mut NoProgress);
+        }
+        ranges.into_iter().map(|range| range.invalid_ids).collect()
+    }
+
+    #[test]
+    fn test_split_ranges() {
+        let ranges = parse_ranges("1-10,20-20,30-34");
+        let chunks = split_ranges(&ranges, 4);
+        let bounds: Vec<(usize, u128, u128)> = chunks
+            .iter()
+            .map(|chunk| (chunk.range, chunk.start, chunk.end))
+            .collect();
+        assert_eq!(
+            bounds,
+            [
+                (0, 1, 4),
+                (0, 5, 8),
+                (0, 9, 10),
+                (1, 20, 20),
+                (2, 30, 33),
+                (2, 34, 34)
+            ]
+        );
+
+        let chunks = split_ranges(&[IdRange::new(u128::MAX - 5, u128::MAX)], 4);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].end, u128::MAX);
+    }
+
+    #[test]
+    fn test_same_for_any_thread_count() {
+        let input = "95-115,998-1012,1188511880-1188511890,222220-222224,1-30000,565653-565659";
+        for rule in [Rule::AtLeastTwice, Rule::parse("palindrome|twice").unwrap()] {
+            let expected = sequential(input, &rule);
+            for threads in [1, 2, 3, 8] {
+                for chunk_size in [1, 7, 1000, MIN_CHUNK] {
+                    let mut ranges = parse_ranges(input);
+                    find_in_chunks(&mut ranges, &rule, threads, chunk_size, &mut NoProgress);
+                    let ids: Vec<Vec<u128>> =
+                        ranges.into_iter().map(|range| range.invalid_ids).collect();
+                    assert_eq!(
+                        ids, expected,
+                        "{} threads, chunks of {}",
+                        threads, chunk_size
+                    );
+                }
+            }
+
+            let mut ranges = parse_ranges(input);
+            find_invalid_ids_parallel(&mut ranges, &rule, 4, &mut NoProgress);
+            let ids: Vec<Vec<u128>> = ranges.into_iter().map(|range| range.invalid_ids).collect();
+            assert_eq!(ids, expected);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+// Digit period checks done with arithmetic on the id itself, no strings.
+// An id has period p when its leading p digits repeated make up the whole id.
+// The free functions work in decimal, Radix has the same checks for any base from 2 to 36.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Radix(u32);
+
+impl Radix {
+    pub const DECIMAL: Radix = Radix(10);
+
+    pub fn new(radix: u32) -> Result<Self, String> {
+        if (2..=36).contains(&radix) {
+            Ok(Radix(radix))
+        } else {
+            Err(format!("Radix must be between 2 and 36, got {}", radix))
+        }
+    }
+
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+
+    // radix^exp, None if it doesn't fit in a u128
+    pub fn pow(&self, exp: u32) -> Option<u128> {
+        (self.0 as u128).checked_pow(exp)
+    }
+
+    pub fn digit_count(&self, id: u128) -> u32 {
+        id.checked_ilog(self.0 as u128).unwrap_or(0) + 1
+    }
+
+    // Multiplier that repeats a `period` digit pattern `repeats` times, e.g. 10101 for 2 and 3
+    pub fn repeat_multiplier(&self, period: u32, repeats: u32) -> u128 {
+        let shift = self.pow(period).expect("Period longer than a u128 id");
+        (1..repeats).fold(1, |multiplier, _| multiplier * shift + 1)
+    }
+
+    // Whether the id is its leading `period` digits repeated, every id has its own length as a period
+    pub fn has_period(&self, id: u128, period: u32) -> bool {
+        let length = self.digit_count(id);
+        if period == 0 || !length.is_multiple_of(period) {
+            return false;
+        }
+        let pattern = id / self.pow(length - period).expect("Shorter than the id");
+        // the repeated pattern can be past u128::MAX for the longest ids, in which case it isn't the id
+        pattern
+            .checked_mul(self.repeat_multiplier(period, length / period))
+            .is_some_and(|repeated| repeated == id)
+    }
+
+    // Length of the shortest repeating pattern, the whole length if nothing repeats
+    pub fn minimal_period(&self, id: u128) -> u32 {
+        let length = self.digit_count(id);
+        (1..length)
+            .find(|&period| self.has_period(id, period))
+            .unwrap_or(length)
+    }
+
+    pub fn is_palindrome(&self, id: u128) -> bool {
+        let length = self.digit_count(id);
+        let radix = self.0 as u128;
+        let digit = |i: u32| (id / self.pow(i).expect("Shorter than the id")) % radix;
+        (0..length / 2).all(|i| digit(i) == digit(length - 1 - i))
+    }
+
+    pub fn parse(&self, text: &str) -> Result<u128, String> {
+        u128::from_str_radix(text, self.0)
+            .map_err(|e| format!("Invalid base {} id {}: {}", self.0, text, e))
+    }
+
+    // Upper case digits, e.g. ABAB in hex
+    pub fn format(&self, id: u128) -> String {
+        let radix = self.0 as u128;
+        let mut digits = Vec::new();
+        let mut rest = id;
+        loop {
+            let digit = char::from_digit((rest % radix) as u32, self.0).expect("Digit below radix");
+            digits.push(digit.to_ascii_uppercase());
+            rest /= radix;
+            if rest == 0 {
+                break;
+            }
+        }
+        digits.iter().rev().collect()
+    }
+}
+
+pub fn digit_count(id: u128) -> u32 {
+    Radix::DECIMAL.digit_count(id)
+}
+
+pub fn repeat_multiplier(period: u32, repeats: u32) -> u128 {
+    Radix::DECIMAL.repeat_multiplier(period, repeats)
+}
+
+pub fn has_period(id: u128, period: u32) -> bool {
+    Radix::DECIMAL.has_period(id, period)
+}
+
+pub fn minimal_period(id: u128) -> u32 {
+    Radix::DECIMAL.minimal_period(id)
+}
+
+// How many times the minimal pattern repeats, 1 if nothing repeats
+pub fn repeat_count(id: u128) -> u32 {
+    digit_count(id) / minimal_period(id)
+}
+
+pub fn is_palindrome(id: u128) -> bool {
+    Radix::DECIMAL.is_palindrome(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::has_repeated_advanced;
+
+    #[test]
+    fn test_has_period() {
+        assert!(has_period(121212, 2));
+        assert!(has_period(121212, 6));
+        assert!(!has_period(121212, 3));
+        assert!(!has_period(121212, 4));
+        assert!(!has_period(121212, 0));
+        assert!(has_period(7, 1));
+        assert!(!has_period(1210, 2));
+    }
+
+    #[test]
+    fn test_minimal_period() {
+        assert_eq!(minimal_period(111111), 1);
+        assert_eq!(minimal_period(121212), 2);
+        assert_eq!(minimal_period(123123), 3);
+        assert_eq!(minimal_period(1234), 4);
+        assert_eq!(minimal_period(0), 1);
+        assert_eq!(repeat_count(123123123), 3);
+        assert_eq!(repeat_count(1234), 1);
+    }
+
+    #[test]
+    fn test_matches_string_version() {
+        for id in (1..200_000).chain(u128::MAX - 1000..=u128::MAX) {
+            assert_eq!(
+                minimal_period(id) < digit_count(id),
+                has_repeated_advanced(id.to_string()),
+                "{}",
+                id
+            );
+        }
+        // 39 digits where repeating the leading digits doesn't fit in a u128
+        assert!(!has_period(u128::MAX, 3));
+        assert!(!has_period(u128::MAX, 13));
+    }
+
+    #[test]
+    fn test_other_radixes() {
+        let hex = Radix::new(16).unwrap();
+        assert!(hex.has_period(0xABAB, 2));
+        assert_eq!(hex.minimal_period(0xABAB), 2);
+        assert_eq!(hex.minimal_period(0xABBA), 4);
+        assert!(hex.is_palindrome(0xABBA));
+        assert_eq!(hex.parse("abab"), Ok(0xABAB));
+        assert_eq!(hex.format(0xABAB), "ABAB");
+        assert!(hex.parse("ABAG").is_err());
+
+        let binary = Radix::new(2).unwrap();
+        assert_eq!(binary.minimal_period(0b101010), 2);
+        assert_eq!(binary.format(0b101010), "101010");
+
+        let base36 = Radix::new(36).unwrap();
+        let id = base36.parse("ZZ9ZZ9").unwrap();
+        assert_eq!(base36.minimal_period(id), 3);
+        assert_eq!(base36.format(id), "ZZ9ZZ9");
+        // 25 digits is the longest base 36 id, repeating 5 of them overflows near u128::MAX
+        assert_eq!(base36.digit_count(u128::MAX), 25);
+        assert!(!base36.has_period(u128::MAX, 5));
+
+        assert!(Radix::new(1).is_err());
+        assert!(Radix::new(37).is_err());
+        assert_eq!(Radix::DECIMAL.format(0), "0");
+    }
+
+    #[test]
+    fn test_is_palindrome() {
+        assert!(is_palindrome(12321));
+        assert!(is_palindrome(1221));
+        assert!(is_palindrome(5));
+        assert!(!is_palindrome(1231));
+        assert!(!is_palindrome(10));
+        assert!(!is_palindrome(u128::MAX));
+    }
+}
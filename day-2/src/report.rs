@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+
+use crate::IdRange;
+use crate::period::Radix;
+use crate::rule::Rule;
+
+// Why an invalid id is invalid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdReport {
+    pub id: u128,
+    // Shortest repeating pattern and its length in digits
+    pub pattern: u128,
+    pub period: u32,
+    pub repeats: u32,
+    // The audited rules that match this id
+    pub flagged_by: Vec<Rule>,
+    // Digits are counted and shown in this radix
+    pub radix: Radix,
+}
+
+impl IdReport {
+    pub fn new(id: u128, rules: &[Rule]) -> Self {
+        IdReport::with_radix(id, rules, Radix::DECIMAL)
+    }
+
+    pub fn with_radix(id: u128, rules: &[Rule], radix: Radix) -> Self {
+        let length = radix.digit_count(id);
+        let period = radix.minimal_period(id);
+        IdReport {
+            id,
+            pattern: id / radix.pow(length - period).expect("Shorter than the id"),
+            period,
+            repeats: length / period,
+            flagged_by: rules
+                .iter()
+                .filter(|rule| rule.matches_in(id, radix))
+                .cloned()
+                .collect(),
+            radix,
+        }
+    }
+
+    // e.g. "1010: pattern 10 x2, flagged by twice, at-least-twice"
+    pub fn line(&self) -> String {
+        let rules: Vec<String> = self
+            .flagged_by
+            .iter()
+            .map(|rule| rule.to_string())
+            .collect();
+        let flagged = if rules.is_empty() {
+            "flagged by none".to_string()
+        } else {
+            format!("flagged by {}", rules.join(", "))
+        };
+        format!(
+            "{}: pattern {} x{}, {}",
+            self.radix.format(self.id),
+            self.radix.format(self.pattern),
+            self.repeats,
+            flagged
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeReport {
+    pub start: u128,
+    pub end: u128,
+    pub radix: Radix,
+    pub ids: Vec<IdReport>,
+}
+
+impl RangeReport {
+    // Classify the ids already found in the range against each rule
+    pub fn new(range: &IdRange, rules: &[Rule]) -> Self {
+        RangeReport {
+            start: range.start,
+            end: range.end,
+            radix: range.radix,
+            ids: range
+                .invalid_ids
+                .iter()
+                .map(|&id| IdReport::with_radix(id, rules, range.radix))
+                .collect(),
+        }
+    }
+
+    // How many ids have each minimal period
+    pub fn periods(&self) -> BTreeMap<u32, usize> {
+        let mut periods = BTreeMap::new();
+        for report in &self.ids {
+            *periods.entry(report.period).or_insert(0) += 1;
+        }
+        periods
+    }
+
+    // e.g. "range 998-1012: 2 IDs, periods {1:1, 2:1}"
+    pub fn summary(&self) -> String {
+        let periods: Vec<String> = self
+            .periods()
+            .iter()
+            .map(|(period, count)| format!("{}:{}", period, count))
+            .collect();
+        format!(
+            "range {}-{}: {} IDs, periods {{{}}}",
+            self.radix.format(self.start),
+            self.radix.format(self.end),
+            self.ids.len(),
+            periods.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::progress::NoProgress;
+
+    fn found_report(start: u128, end: u128, rule: &Rule) -> RangeReport {
+        let mut range = IdRange::new(start, end);
+        range.find_invalid_ids(rule, &mut NoProgress);
+        RangeReport::new(&range, &[Rule::Twice, Rule::AtLeastTwice, Rule::Palindrome])
+    }
+
+    #[test]
+    fn test_summary() {
+        let report = found_report(998, 1012, &Rule::AtLeastTwice);
+        assert_eq!(
+            report.summary(),
+            "range 998-1012: 2 IDs, periods {1:1, 2:1}"
+        );
+        assert_eq!(
+            report.ids[0].line(),
+            "999: pattern 9 x3, flagged by at-least-twice, palindrome"
+        );
+        assert_eq!(
+            report.ids[1].line(),
+            "1010: pattern 10 x2, flagged by twice, at-least-twice"
+        );
+
+        assert_eq!(
+            found_report(1, 9, &Rule::AtLeastTwice).summary(),
+            "range 1-9: 0 IDs, periods {}"
+        );
+    }
+
+    #[test]
+    fn test_id_report() {
+        let report = IdReport::new(123123123, &[Rule::Twice, Rule::Exactly(3)]);
+        assert_eq!((report.pattern, report.period, report.repeats), (123, 3, 3));
+        assert_eq!(report.flagged_by, vec![Rule::Exactly(3)]);
+
+        // 111111 is reported by its shortest pattern
+        let report = IdReport::new(111111, &[]);
+        assert_eq!((report.pattern, report.period, report.repeats), (1, 1, 6));
+        assert_eq!(report.line(), "111111: pattern 1 x6, flagged by none");
+    }
+
+    #[test]
+    fn test_hex_report() {
+        let hex = Radix::new(16).unwrap();
+        let mut range = IdRange::with_radix(0xABA0, 0xABBF, hex);
+        range.find_invalid_ids(&Rule::AtLeastTwice, &mut NoProgress);
+        let report = RangeReport::new(&range, &[Rule::Twice, Rule::Palindrome]);
+        assert_eq!(report.summary(), "range ABA0-ABBF: 1 IDs, periods {2:1}");
+        assert_eq!(
+            report.ids[0].line(),
+            "ABAB: pattern AB x2, flagged by twice"
+        );
+    }
+}
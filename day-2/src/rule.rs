@@ -0,0 +1,267 @@
+use std::fmt;
+
+use crate::period::Radix;
+
+// Which ids count as invalid, picked at runtime with Rule::parse
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    // A pattern repeated exactly twice, e.g. 1212 (part 1)
+    Twice,
+    // A pattern repeated two or more times, e.g. 121212 (part 2)
+    AtLeastTwice,
+    // A pattern repeated exactly this many times, 111111 is 3 repeats of 11 as well as 6 of 1
+    Exactly(u32),
+    // A pattern of at least this many digits repeated two or more times
+    MinPatternLength(u32),
+    // Reads the same both ways, e.g. 12321
+    Palindrome,
+    // Every rule matches
+    And(Vec<Rule>),
+    // Any rule matches
+    Or(Vec<Rule>),
+}
+
+impl Rule {
+    pub fn matches(&self, id: u128) -> bool {
+        self.matches_in(id, Radix::DECIMAL)
+    }
+
+    // Same rules on the digits of the id written in another radix, e.g. ABAB in hex is twice
+    pub fn matches_in(&self, id: u128, radix: Radix) -> bool {
+        let length = radix.digit_count(id);
+        match self {
+            Rule::Twice => Rule::Exactly(2).matches_in(id, radix),
+            Rule::AtLeastTwice => radix.minimal_period(id) < length,
+            Rule::Exactly(repeats) => {
+                *repeats > 0
+                    && length.is_multiple_of(*repeats)
+                    && radix.has_period(id, length / repeats)
+            }
+            Rule::MinPatternLength(min) => {
+                ((*min).max(1)..length).any(|period| radix.has_period(id, period))
+            }
+            Rule::Palindrome => radix.is_palindrome(id),
+            Rule::And(rules) => rules.iter().all(|rule| rule.matches_in(id, radix)),
+            Rule::Or(rules) => rules.iter().any(|rule| rule.matches_in(id, radix)),
+        }
+    }
+
+    // Periods whose repeated patterns are exactly the matching ids of `length` digits,
+    // None when the rule can't be built from repeated patterns and ids have to be checked.
+    // Only the longest periods are kept since a pattern with period p also has period 2p..
+    pub fn periods(&self, length: u32) -> Option<Vec<u32>> {
+        let periods = match self {
+            Rule::Twice => return Rule::Exactly(2).periods(length),
+            Rule::AtLeastTwice => return Rule::MinPatternLength(1).periods(length),
+            Rule::Exactly(repeats) => {
+                if *repeats > 0 && length.is_multiple_of(*repeats) {
+                    vec![length / repeats]
+                } else {
+                    Vec::new()
+                }
+            }
+            Rule::MinPatternLength(min) => ((*min).max(1)..length)
+                .filter(|&period| length.is_multiple_of(period))
+                .collect(),
+            Rule::Palindrome | Rule::And(_) => return None,
+            Rule::Or(rules) => {
+                let mut periods = Vec::new();
+                for rule in rules {
+                    periods.extend(rule.periods(length)?);
+                }
+                periods
+            }
+        };
+        Some(longest_periods(periods))
+    }
+
+    // Rules joined with | (or) and & (and), & binds tighter, e.g. "twice|exactly:3&min-pattern:2"
+    pub fn parse(text: &str) -> Result<Rule, String> {
+        let mut any = text
+            .split('|')
+            .map(|part| {
+                let mut all = part
+                    .split('&')
+                    .map(parse_single_rule)
+                    .collect::<Result<Vec<Rule>, String>>()?;
+                Ok(if all.len() == 1 {
+                    all.remove(0)
+                } else {
+                    Rule::And(all)
+                })
+            })
+            .collect::<Result<Vec<Rule>, String>>()?;
+        Ok(if any.len() == 1 {
+            any.remove(0)
+        } else {
+            Rule::Or(any)
+        })
+    }
+}
+
+fn parse_single_rule(text: &str) -> Result<Rule, String> {
+    let text = text.trim();
+    let (name, value) = match text.split_once(':') {
+        Some((name, value)) => {
+            let value = value
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid number in rule {}", text))?;
+            (name.trim(), Some(value))
+        }
+        None => (text, None),
+    };
+
+    match (name, value) {
+        ("twice", None) => Ok(Rule::Twice),
+        ("at-least-twice", None) => Ok(Rule::AtLeastTwice),
+        ("palindrome", None) => Ok(Rule::Palindrome),
+        // an id is always its own pattern once, so fewer than 2 repeats isn't a repetition
+        ("exactly", Some(repeats)) if repeats < 2 => Err(format!(
+            "Rule {} needs at least 2 repeats, got {}",
+            name, repeats
+        )),
+        ("exactly", Some(repeats)) => Ok(Rule::Exactly(repeats)),
+        ("min-pattern", Some(min)) => Ok(Rule::MinPatternLength(min)),
+        ("exactly" | "min-pattern", None) => Err(format!("Rule {} needs a number", name)),
+        _ => Err(format!("Unknown rule {}", text)),
+    }
+}
+
+// Drop any period that divides another, its ids are already covered by the longer one
+fn longest_periods(mut periods: Vec<u32>) -> Vec<u32> {
+    periods.sort_unstable();
+    periods.dedup();
+    periods
+        .iter()
+        .copied()
+        .filter(|&period| {
+            !periods
+                .iter()
+                .any(|&other| other != period && other.is_multiple_of(period))
+        })
+        .collect()
+}
+
+impl fmt::Display for Rule {
+    // Same syntax Rule::parse reads
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let join = |rules: &[Rule], separator: &str| {
+            rules
+                .iter()
+                .map(|rule| rule.to_string())
+                .collect::<Vec<String>>()
+                .join(separator)
+        };
+        match self {
+            Rule::Twice => write!(f, "twice"),
+            Rule::AtLeastTwice => write!(f, "at-least-twice"),
+            Rule::Exactly(repeats) => write!(f, "exactly:{}", repeats),
+            Rule::MinPatternLength(min) => write!(f, "min-pattern:{}", min),
+            Rule::Palindrome => write!(f, "palindrome"),
+            Rule::And(rules) => write!(f, "{}", join(rules, "&")),
+            Rule::Or(rules) => write!(f, "{}", join(rules, "|")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        assert!(Rule::Twice.matches(1212));
+        assert!(!Rule::Twice.matches(121212));
+        assert!(Rule::AtLeastTwice.matches(121212));
+        assert!(Rule::AtLeastTwice.matches(111));
+        assert!(!Rule::AtLeastTwice.matches(7));
+
+        assert!(Rule::Exactly(3).matches(121212));
+        assert!(Rule::Exactly(3).matches(111111));
+        assert!(!Rule::Exactly(3).matches(1212));
+        assert!(!Rule::Exactly(0).matches(1212));
+
+        assert!(Rule::MinPatternLength(2).matches(121212));
+        assert!(Rule::MinPatternLength(2).matches(111111));
+        assert!(!Rule::MinPatternLength(2).matches(111));
+
+        assert!(Rule::Palindrome.matches(12321));
+        assert!(Rule::Palindrome.matches(5));
+        assert!(!Rule::Palindrome.matches(1232));
+    }
+
+    #[test]
+    fn test_matches_in_radix() {
+        let hex = Radix::new(16).unwrap();
+        assert!(Rule::Twice.matches_in(0xABAB, hex));
+        assert!(!Rule::Twice.matches(0xABAB));
+        assert!(Rule::Palindrome.matches_in(0xABCBA, hex));
+        assert!(!Rule::AtLeastTwice.matches_in(0xABBA, hex));
+
+        // 10 is 1010 in binary
+        let binary = Radix::new(2).unwrap();
+        assert!(Rule::Twice.matches_in(10, binary));
+        assert!(!Rule::Twice.matches(10));
+    }
+
+    #[test]
+    fn test_and_or() {
+        let rule = Rule::parse("twice&palindrome|exactly:3").unwrap();
+        assert_eq!(
+            rule,
+            Rule::Or(vec![
+                Rule::And(vec![Rule::Twice, Rule::Palindrome]),
+                Rule::Exactly(3)
+            ])
+        );
+        assert!(rule.matches(1111));
+        assert!(!rule.matches(1212));
+        assert!(rule.matches(121212));
+        assert_eq!(rule.to_string(), "twice&palindrome|exactly:3");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Rule::parse(" min-pattern: 2 "),
+            Ok(Rule::MinPatternLength(2))
+        );
+        assert_eq!(
+            Rule::parse("exactly").unwrap_err(),
+            "Rule exactly needs a number"
+        );
+        assert_eq!(
+            Rule::parse("exactly:x").unwrap_err(),
+            "Invalid number in rule exactly:x"
+        );
+        assert_eq!(
+            Rule::parse("exactly:0").unwrap_err(),
+            "Rule exactly needs at least 2 repeats, got 0"
+        );
+        assert_eq!(
+            Rule::parse("twice|exactly: 1").unwrap_err(),
+            "Rule exactly needs at least 2 repeats, got 1"
+        );
+        assert_eq!(Rule::parse("exactly:2"), Ok(Rule::Exactly(2)));
+        assert_eq!(Rule::parse("twice:2").unwrap_err(), "Unknown rule twice:2");
+        assert_eq!(Rule::parse("twice|").unwrap_err(), "Unknown rule ");
+    }
+
+    #[test]
+    fn test_periods() {
+        assert_eq!(Rule::Twice.periods(6), Some(vec![3]));
+        assert_eq!(Rule::Twice.periods(5), Some(vec![]));
+        assert_eq!(Rule::AtLeastTwice.periods(12), Some(vec![4, 6]));
+        assert_eq!(Rule::MinPatternLength(5).periods(12), Some(vec![6]));
+        assert_eq!(
+            Rule::Or(vec![Rule::Exactly(3), Rule::Exactly(4)]).periods(12),
+            Some(vec![3, 4])
+        );
+        assert_eq!(Rule::Palindrome.periods(4), None);
+        assert_eq!(
+            Rule::Or(vec![Rule::Twice, Rule::Palindrome]).periods(4),
+            None
+        );
+    }
+}
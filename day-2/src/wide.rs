@@ -0,0 +1,303 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::period::Radix;
+
+// Unsigned integer of any width for ids and sums past u128, only the operations we need
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BigUint {
+    // least significant limb first, never any zero limbs at the end so zero is empty
+    limbs: Vec<u64>,
+}
+
+impl BigUint {
+    pub const ZERO: BigUint = BigUint { limbs: Vec::new() };
+
+    fn from_limbs(mut limbs: Vec<u64>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigUint { limbs }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [low] => Some(low as u128),
+            [low, high] => Some(((high as u128) << 64) | low as u128),
+            _ => None,
+        }
+    }
+
+    // radix^exp
+    pub fn pow(radix: Radix, exp: u32) -> BigUint {
+        let base = BigUint::from(radix.value() as u128);
+        (0..exp).fold(BigUint::from(1), |power, _| power.mul(&base))
+    }
+
+    pub fn add(&self, other: &BigUint) -> BigUint {
+        let mut limbs = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+        let mut carry = false;
+        for i in 0..self.limbs.len().max(other.limbs.len()) {
+            let a = self.limbs.get(i).copied().unwrap_or(0);
+            let b = other.limbs.get(i).copied().unwrap_or(0);
+            let (sum, overflow1) = a.overflowing_add(b);
+            let (sum, overflow2) = sum.overflowing_add(carry as u64);
+            limbs.push(sum);
+            carry = overflow1 || overflow2;
+        }
+        limbs.push(carry as u64);
+        BigUint::from_limbs(limbs)
+    }
+
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        if self < other {
+            return None;
+        }
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = false;
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let b = other.limbs.get(i).copied().unwrap_or(0);
+            let (diff, overflow1) = a.overflowing_sub(b);
+            let (diff, overflow2) = diff.overflowing_sub(borrow as u64);
+            limbs.push(diff);
+            borrow = overflow1 || overflow2;
+        }
+        Some(BigUint::from_limbs(limbs))
+    }
+
+    pub fn mul(&self, other: &BigUint) -> BigUint {
+        // schoolbook on 64 bit limbs
+        let mut product = vec![0u64; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry: u128 = 0;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let current = product[i + j] as u128 + a as u128 * b as u128 + carry;
+                product[i + j] = current as u64;
+                carry = current >> 64;
+            }
+            product[i + other.limbs.len()] = carry as u64;
+        }
+        BigUint::from_limbs(product)
+    }
+
+    // Returns the quotient and remainder
+    pub fn div_rem_u64(&self, divisor: u64) -> (BigUint, u64) {
+        let mut limbs = vec![0u64; self.limbs.len()];
+        let mut remainder: u128 = 0;
+        for (limb, &value) in limbs.iter_mut().zip(&self.limbs).rev() {
+            let current = (remainder << 64) | value as u128;
+            *limb = (current / divisor as u128) as u64;
+            remainder = current % divisor as u128;
+        }
+        (BigUint::from_limbs(limbs), remainder as u64)
+    }
+
+    // Long division one bit at a time, plenty for ids of a few hundred bits
+    pub fn div_rem(&self, divisor: &BigUint) -> (BigUint, BigUint) {
+        assert!(!divisor.is_zero(), "Division by zero");
+        let mut quotient = vec![0u64; self.limbs.len()];
+        let mut remainder = BigUint::ZERO;
+        for bit in (0..self.limbs.len() * 64).rev() {
+            remainder = remainder.add(&remainder);
+            if self.limbs[bit / 64] >> (bit % 64) & 1 == 1 {
+                remainder = remainder.add(&BigUint::from(1));
+            }
+            if let Some(rest) = remainder.checked_sub(divisor) {
+                remainder = rest;
+                quotient[bit / 64] |= 1 << (bit % 64);
+            }
+        }
+        (BigUint::from_limbs(quotient), remainder)
+    }
+
+    pub fn div_ceil(&self, divisor: &BigUint) -> BigUint {
+        let (quotient, remainder) = self.div_rem(divisor);
+        if remainder.is_zero() {
+            quotient
+        } else {
+            quotient.add(&BigUint::from(1))
+        }
+    }
+
+    pub fn digit_count(&self, radix: Radix) -> u32 {
+        let mut length = 1;
+        let mut rest = self.div_rem_u64(radix.value() as u64).0;
+        while !rest.is_zero() {
+            length += 1;
+            rest = rest.div_rem_u64(radix.value() as u64).0;
+        }
+        length
+    }
+
+    pub fn parse(text: &str, radix: Radix) -> Result<BigUint, String> {
+        let invalid = || format!("Invalid base {} id {}", radix.value(), text);
+        if text.is_empty() {
+            return Err(invalid());
+        }
+        let base = BigUint::from(radix.value() as u128);
+        text.chars().try_fold(BigUint::ZERO, |value, ch| {
+            let digit = ch.to_digit(radix.value()).ok_or_else(invalid)?;
+            Ok(value.mul(&base).add(&BigUint::from(digit as u128)))
+        })
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // no zero limbs at the end, so more limbs is always bigger
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(value: u128) -> Self {
+        BigUint::from_limbs(vec![value as u64, (value >> 64) as u64])
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // peel off 19 decimal digits at a time
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut chunks = Vec::new();
+        let mut rest = self.clone();
+        loop {
+            let (quotient, remainder) = rest.div_rem_u64(CHUNK);
+            chunks.push(remainder);
+            rest = quotient;
+            if rest.is_zero() {
+                break;
+            }
+        }
+        let mut text = chunks.pop().unwrap_or(0).to_string();
+        for chunk in chunks.iter().rev() {
+            text.push_str(&format!("{:019}", chunk));
+        }
+        f.pad(&text)
+    }
+}
+
+// What to do with a sum that doesn't fit in a u128
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    Error,
+    // Stop at u128::MAX
+    Saturate,
+    // Keep every digit
+    Widen,
+}
+
+impl OverflowPolicy {
+    pub fn parse(text: &str) -> Result<OverflowPolicy, String> {
+        match text.trim() {
+            "error" => Ok(OverflowPolicy::Error),
+            "saturate" => Ok(OverflowPolicy::Saturate),
+            "widen" => Ok(OverflowPolicy::Widen),
+            _ => Err(format!("Unknown overflow policy {}", text)),
+        }
+    }
+
+    pub fn apply(&self, value: BigUint) -> Result<BigUint, String> {
+        if value.to_u128().is_some() {
+            return Ok(value);
+        }
+        match self {
+            OverflowPolicy::Error => Err(format!("{} doesn't fit in a u128", value)),
+            OverflowPolicy::Saturate => Ok(BigUint::from(u128::MAX)),
+            OverflowPolicy::Widen => Ok(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let max = BigUint::from(u128::MAX);
+        let sum = max.add(&BigUint::from(1));
+        assert_eq!(sum.to_u128(), None);
+        assert_eq!(sum.checked_sub(&BigUint::from(1)), Some(max.clone()));
+        assert_eq!(BigUint::from(1).checked_sub(&BigUint::from(2)), None);
+        assert_eq!(sum.checked_sub(&sum), Some(BigUint::ZERO));
+
+        let square = max.mul(&max);
+        // (2^128 - 1)^2 = 2^256 - 2^129 + 1
+        assert_eq!(
+            square.to_string(),
+            "115792089237316195423570985008687907852589419931798687112530834793049593217025"
+        );
+        assert!(square > max);
+        assert_eq!(square.div_rem(&max), (max.clone(), BigUint::ZERO));
+        assert_eq!(
+            square.add(&BigUint::from(5)).div_rem(&max),
+            (max.clone(), BigUint::from(5))
+        );
+        assert_eq!(square.div_ceil(&sum), max);
+        assert_eq!(BigUint::from(7).div_rem(&BigUint::from(9)).0, BigUint::ZERO);
+    }
+
+    #[test]
+    fn test_past_u128() {
+        let decimal = Radix::DECIMAL;
+        let power = BigUint::pow(decimal, 50);
+        assert_eq!(power.to_string(), format!("1{}", "0".repeat(50)));
+        assert_eq!(power.digit_count(decimal), 51);
+        assert_eq!(BigUint::ZERO.digit_count(decimal), 1);
+        assert_eq!(
+            BigUint::parse(&power.to_string(), decimal),
+            Ok(power.clone())
+        );
+        assert_eq!(
+            BigUint::parse("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF", Radix::new(16).unwrap()),
+            Ok(BigUint::from(u128::MAX)
+                .mul(&BigUint::from(16))
+                .add(&BigUint::from(15)))
+        );
+        assert!(BigUint::parse("12a", decimal).is_err());
+        assert!(BigUint::parse("", decimal).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(BigUint::ZERO.to_string(), "0");
+        assert_eq!(BigUint::from(1234).to_string(), "1234");
+        assert_eq!(BigUint::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(
+            BigUint::from(10u128.pow(19)).to_string(),
+            "10000000000000000000"
+        );
+    }
+
+    #[test]
+    fn test_overflow_policy() {
+        let fits = BigUint::from(u128::MAX);
+        let past = fits.add(&BigUint::from(1));
+        for policy in ["error", "saturate", "widen"] {
+            let policy = OverflowPolicy::parse(policy).unwrap();
+            assert_eq!(policy.apply(fits.clone()), Ok(fits.clone()));
+        }
+        assert!(OverflowPolicy::Error.apply(past.clone()).is_err());
+        assert_eq!(
+            OverflowPolicy::Saturate.apply(past.clone()),
+            Ok(fits.clone())
+        );
+        assert_eq!(OverflowPolicy::Widen.apply(past.clone()), Ok(past));
+        assert!(OverflowPolicy::parse("wrap").is_err());
+    }
+}
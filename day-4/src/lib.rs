@@ -0,0 +1,356 @@
+use std::{char, collections::VecDeque};
+
+use aoc_common::image::{Image, Rgb};
+use aoc_common::progress::{NoProgress, Progress};
+use aoc_common::terminal::{Cell, Colour, Frame, Viewport};
+
+// Total number of items removed once the grid stops changing
+pub fn solve(input: &str) -> usize {
+    let (_iterations, total_removed) = remove_until_stable(parse_grid(input), &mut NoProgress);
+    total_removed
+}
+
+pub fn parse_grid(input: &str) -> Vec<Vec<char>> {
+    input.lines().map(|line| line.chars().collect()).collect()
+}
+
+// Keep removing accessible items until none are left, returns (iterations, total removed)
+pub fn remove_until_stable(input: Vec<Vec<char>>, progress: &mut dyn Progress) -> (usize, usize) {
+    let mut value = perform_processing(input);
+
+    let mut total_removed = 0;
+
+    let mut iterations = 0;
+
+    while value.items_removed > 0 {
+        value = perform_processing(value.output).replace_xs_with_dots();
+        iterations += 1;
+        total_removed += value.items_removed;
+        progress.advance(1);
+    }
+    progress.finish();
+
+    (iterations, total_removed)
+}
+
+// One removal iteration at a time, each item is the grid with that step's removals marked X
+pub struct RemovalSteps {
+    grid: Option<Vec<Vec<char>>>,
+}
+
+impl RemovalSteps {
+    pub fn new(grid: Vec<Vec<char>>) -> Self {
+        RemovalSteps { grid: Some(grid) }
+    }
+}
+
+impl Iterator for RemovalSteps {
+    type Item = ProcessedOutput;
+
+    fn next(&mut self) -> Option<ProcessedOutput> {
+        let processed = perform_processing(self.grid.take()?);
+        if processed.items_removed == 0 {
+            return None;
+        }
+        let cleared = ProcessedOutput {
+            output: processed.output.clone(),
+            items_removed: processed.items_removed,
+        }
+        .replace_xs_with_dots();
+        self.grid = Some(cleared.output);
+        Some(processed)
+    }
+}
+
+// Rolls green, items about to be removed highlighted red, empty space grey
+pub fn cell_style(c: char) -> Cell {
+    match c {
+        '@' => Cell::new('@', Colour::Green),
+        'X' => Cell {
+            highlighted: true,
+            ..Cell::new('X', Colour::Red)
+        },
+        '.' => Cell::new('.', Colour::Grey),
+        other => Cell::new(other, Colour::Default),
+    }
+}
+
+pub fn render_grid(grid: &[Vec<char>], viewport: &Viewport) -> Frame {
+    Frame::from_grid(grid, viewport, cell_style)
+}
+
+// Empty space, rolls and items about to be removed
+const IMAGE_PALETTE: [Rgb; 3] = [[240, 240, 240], [40, 140, 60], [210, 40, 40]];
+
+// One pixel per cell, upscale the result for anything bigger
+pub fn grid_image(grid: &[Vec<char>]) -> Image {
+    let width = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+    Image::from_fn(
+        width,
+        grid.len(),
+        IMAGE_PALETTE.to_vec(),
+        |x, y| match grid[y].get(x) {
+            Some('@') => 1,
+            Some('X') => 2,
+            _ => 0,
+        },
+    )
+}
+
+pub struct ProcessedOutput {
+    pub output: Vec<Vec<char>>,
+    pub items_removed: usize,
+}
+
+impl ProcessedOutput {
+    pub fn replace_xs_with_dots(mut self) -> Self {
+        for row in self.output.iter_mut() {
+            for c in row.iter_mut() {
+                if *c == 'X' {
+                    *c = '.';
+                }
+            }
+        }
+        self
+    }
+}
+
+pub fn perform_processing(input: Vec<Vec<char>>) -> ProcessedOutput {
+    //three buffers
+    let mut line_above_buffer: Vec<char>;
+    //minus one
+    let mut current_line_buffer: Vec<char> = Vec::new();
+    //row of interest
+    let mut next_line_buffer: Vec<char>;
+    //plus one
+
+    let mut output: Vec<Vec<char>> = Vec::new();
+    let mut x_count = 0;
+
+    for row in 0..input.len() {
+        //first row special case
+        line_above_buffer = if row > 0 {
+            current_line_buffer.clone()
+        } else {
+            Vec::new()
+        };
+        current_line_buffer = input[row].clone();
+        //last row special case
+        next_line_buffer = if row + 1 < input.len() {
+            input[row + 1].clone()
+        } else {
+            Vec::new()
+        };
+
+        // println!(
+        //     "Above: {:?}\nCurrent: {:?}\nBelow: {:?}\n",
+        //     line_above_buffer, current_line_buffer, next_line_buffer
+        // );
+        let processed = process_line(&line_above_buffer, &current_line_buffer, &next_line_buffer);
+
+        //count X in processed line
+        let line_x_count = processed.iter().filter(|&&c| c == 'X').count();
+
+        output.push(processed);
+
+        x_count += line_x_count;
+    }
+
+    ProcessedOutput {
+        output: output,
+        items_removed: x_count,
+    }
+}
+
+fn process_line(above: &Vec<char>, current: &Vec<char>, below: &Vec<char>) -> Vec<char> {
+    let max_len = current.len(); //assuming constant line length
+    let mut result_line: Vec<char> = current.clone();
+
+    let max_surrounding = 3; //max number of surrounding cells containing paper
+
+    let mut window = Window::new();
+    window.init(&'.');
+
+    for col in 0..max_len {
+        // if first col special case we need to init the window
+        if col == 0 {
+            let column = get_column(above, current, below, col);
+            window.add_column(column);
+        }
+
+        //push in the next column to the right
+        if col + 1 < max_len {
+            let next_column = get_column(above, current, below, col + 1);
+            window.add_column(next_column);
+        } else {
+            //push in empty column if we are at the edge
+            window.add_column(Column {
+                top: '.',
+                middle: '.',
+                bottom: '.',
+            });
+        }
+
+        //println!("Window at col {}: {:?}", col, window.deque);
+
+        if window.get_middle_value() == '.' {
+            //current cell is empty, skip
+            continue;
+        }
+
+        let occupied_count = window.count_occupied();
+
+        if occupied_count <= max_surrounding {
+            result_line[col] = 'X';
+        }
+    }
+    result_line
+}
+
+#[derive(Debug)]
+struct Column {
+    top: char,
+    middle: char,
+    bottom: char,
+}
+
+fn get_column(above: &Vec<char>, current: &Vec<char>, below: &Vec<char>, col: usize) -> Column {
+    let top = if !above.is_empty() { above[col] } else { '.' };
+    let middle = current[col];
+    let bottom = if !below.is_empty() { below[col] } else { '.' };
+    Column {
+        top,
+        middle,
+        bottom,
+    }
+}
+
+struct Window {
+    deque: VecDeque<Column>,
+}
+
+impl Window {
+    fn new() -> Self {
+        Self {
+            deque: VecDeque::with_capacity(3),
+        }
+    }
+
+    fn init(&mut self, char: &char) {
+        for _ in 0..3 {
+            self.deque.push_back(Column {
+                top: *char,
+                middle: *char,
+                bottom: *char,
+            });
+        }
+    }
+
+    fn get_middle_value(&self) -> char {
+        if let Some(middle_column) = self.deque.get(1) {
+            middle_column.middle
+        } else {
+            '.'
+        }
+    }
+
+    fn add_column(&mut self, column: Column) {
+        if self.deque.len() == 3 {
+            self.deque.pop_front();
+        }
+        self.deque.push_back(column);
+    }
+
+    fn count_occupied(&self) -> usize {
+        let mut count = 0;
+        // count across all three columns in the window
+        for (idx, col) in self.deque.iter().enumerate() {
+            if col.top != '.' {
+                count += 1;
+            }
+            if idx != 1 && col.middle != '.' {
+                count += 1;
+            }
+            if col.bottom != '.' {
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_init() {
+        let mut window = Window::new();
+        window.init(&'.');
+        assert_eq!(window.deque.len(), 3);
+        for col in window.deque.iter() {
+            assert_eq!(col.top, '.');
+            assert_eq!(col.middle, '.');
+            assert_eq!(col.bottom, '.');
+        }
+    }
+    #[test]
+    fn test_window_count() {
+        let mut window = Window::new();
+        window.add_column(Column {
+            top: 'A',
+            middle: 'B',
+            bottom: '.',
+        });
+        window.add_column(Column {
+            top: 'B',
+            middle: 'C',
+            bottom: 'D',
+        });
+        window.add_column(Column {
+            top: '.',
+            middle: '.',
+            bottom: 'E',
+        });
+        assert_eq!(window.count_occupied(), 5);
+    }
+    #[test]
+    fn test_remove_until_stable() {
+        let input =
+            std::fs::read_to_string("./input_short.txt").expect("Failed to read input file");
+        let (iterations, total_removed) = remove_until_stable(parse_grid(&input), &mut NoProgress);
+        assert_eq!(iterations, 10);
+        assert_eq!(total_removed, 43);
+    }
+
+    #[test]
+    fn test_removal_steps() {
+        let input =
+            std::fs::read_to_string("./input_short.txt").expect("Failed to read input file");
+        let steps: Vec<usize> = RemovalSteps::new(parse_grid(&input))
+            .map(|step| step.items_removed)
+            .collect();
+        assert_eq!(steps.iter().sum::<usize>(), 43);
+        assert_eq!(steps[0], 13);
+    }
+
+    #[test]
+    fn test_render_grid() {
+        let grid = parse_grid("@X.\n..@\n");
+        let frame = render_grid(&grid, &Viewport::new(0, 0, 2, 2));
+        assert_eq!(frame.to_plain(), "@X\n..\n");
+        assert!(frame.get(1, 0).unwrap().highlighted);
+        assert_eq!(frame.get(0, 0).unwrap().colour, Colour::Green);
+    }
+
+    #[test]
+    fn test_grid_image() {
+        let image = grid_image(&parse_grid("@X.\n.@\n"));
+        assert_eq!((image.width, image.height), (3, 2));
+        assert_eq!(image.get(0, 0), 1);
+        assert_eq!(image.get(1, 0), 2);
+        assert_eq!(image.get(2, 0), 0);
+        // short rows are padded with empty space
+        assert_eq!(image.get(2, 1), 0);
+    }
+}
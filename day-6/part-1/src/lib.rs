@@ -0,0 +1,138 @@
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Operator {
+    Add,
+    Multiply,
+    NotSet,
+}
+
+impl Operator {
+    pub fn from_char(op_char: char) -> Operator {
+        match op_char {
+            '+' => Operator::Add,
+            '*' => Operator::Multiply,
+            _ => panic!("Invalid operator character: {}", op_char),
+        }
+    }
+}
+
+pub struct Calculation {
+    pub values: Vec<u32>,
+    pub operator: Operator,
+}
+
+impl Calculation {
+    pub fn new(values: Vec<u32>, operator: Operator) -> Self {
+        Calculation { values, operator }
+    }
+
+    pub fn calculate(&self) -> u128 {
+        match self.operator {
+            Operator::Add => self.values.iter().map(|&v| v as u128).sum(),
+            Operator::Multiply => self.values.iter().map(|&v| v as u128).product(),
+            Operator::NotSet => panic!("Operator not set for calculation"),
+        }
+    }
+}
+
+// Split the input into its value lines and operator lines
+pub fn parse_input(input: &str) -> (Vec<Vec<u32>>, Vec<Vec<Operator>>) {
+    let mut values_vec: Vec<Vec<u32>> = Vec::new();
+    let mut operators_vec: Vec<Vec<Operator>> = Vec::new();
+
+    for line in input.lines() {
+        // if lines contains values
+        if !line.contains('+') && !line.contains('*') {
+            let values = process_values_line(line);
+            //println!("Processed values line: {:?}", values);
+            values_vec.push(values);
+        } else {
+            let operators = process_operator_line(line);
+            //println!("Processed operators line: {:?}", &operators);
+            operators_vec.push(operators);
+        }
+    }
+
+    (values_vec, operators_vec)
+}
+
+// One calculation per operator, taking the value in the same column of every values line
+pub fn build_calculations(values_vec: &[Vec<u32>], operators: &[Operator]) -> Vec<Calculation> {
+    let mut calculations: Vec<Calculation> = Vec::new();
+    for (i, operator) in operators.iter().enumerate() {
+        let mut values_for_calc: Vec<u32> = Vec::new();
+        for values_line in values_vec {
+            if i < values_line.len() {
+                values_for_calc.push(values_line[i]);
+            } else {
+                panic!("Mismatch in values line length for operator index {}", i);
+            }
+        }
+        calculations.push(Calculation::new(values_for_calc, operator.clone()));
+    }
+    calculations
+}
+
+// Sum of the answers to every calculation
+pub fn solve(input: &str) -> u128 {
+    let (values_vec, operators_vec) = parse_input(input);
+    build_calculations(&values_vec, &operators_vec[0])
+        .iter()
+        .map(|calc| calc.calculate())
+        .sum()
+}
+
+pub fn process_values_line(line: &str) -> Vec<u32> {
+    line.split_whitespace()
+        .map(|s| {
+            s.trim()
+                .parse::<u32>()
+                .expect("Invalid number in values line")
+        })
+        .collect()
+}
+
+pub fn process_operator_line(line: &str) -> Vec<Operator> {
+    line.split_whitespace()
+        .map(|s| {
+            let ch = s.trim().chars().next().expect("Empty operator string");
+            Operator::from_char(ch)
+        })
+        .collect()
+}
+
+pub fn lengths<T>(vec: &Vec<Vec<T>>) -> Vec<usize> {
+    vec.iter().map(|v| v.len()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_process_values_line() {
+        let line = "10 20 30 40";
+        let values = process_values_line(line);
+        assert_eq!(values, vec![10, 20, 30, 40]);
+    }
+    #[test]
+    fn test_process_operator_line() {
+        let line = "+ * +";
+        let operators = process_operator_line(line);
+        assert_eq!(operators.len(), 3);
+        let expected = vec![Operator::Add, Operator::Multiply, Operator::Add];
+        assert_eq!(operators, expected);
+    }
+    #[test]
+    fn test_calculation_add() {
+        let calc = Calculation::new(vec![1, 2, 3, 4], Operator::Add);
+        let result = calc.calculate();
+        assert_eq!(result, 10);
+        let calc_mul = Calculation::new(vec![1, 2, 3, 4], Operator::Multiply);
+        let result_mul = calc_mul.calculate();
+        assert_eq!(result_mul, 24);
+    }
+    #[test]
+    fn test_solve() {
+        let input = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n";
+        assert_eq!(solve(input), 4277556);
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::HashSet;
+
+use aoc_common::progress::{NoProgress, Progress};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Position {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+}
+
+impl Position {
+    fn from_str(position_str: &str) -> Self {
+        let parts: Vec<&str> = position_str.split(',').collect();
+        let values: Vec<u32> = parts
+            .iter()
+            .map(|s| s.parse::<u32>().expect("Invalid number"))
+            .collect();
+        Self {
+            x: values[0],
+            y: values[1],
+            z: values[2],
+        }
+    }
+
+    // Leave unrooted so we dont worry about floats
+    pub fn distance_to(&self, other: &Position) -> i64 {
+        let dx = self.x.abs_diff(other.x) as i64;
+        let dy = self.y.abs_diff(other.y) as i64;
+        let dz = self.z.abs_diff(other.z) as i64;
+
+        dx * dx + dy * dy + dz * dz
+    }
+}
+
+// Number of closest pairs to connect
+pub const CONNECTIONS: usize = 1000;
+
+#[derive(Debug)]
+pub struct JunctionBox {
+    pub id: u32,
+    pub position: Position,
+}
+
+#[derive(Debug)]
+pub struct Distance {
+    pub ids: (u32, u32),
+    pub value: i64,
+}
+
+// Distance between every pair of boxes, ids are 1-based
+pub fn generate_distances(boxes: &[JunctionBox], progress: &mut dyn Progress) -> Vec<Distance> {
+    let mut distances: Vec<Distance> = Vec::new();
+    for i in 0..boxes.len() {
+        for j in (i + 1)..boxes.len() {
+            let pos1 = &boxes[i];
+            let pos2 = &boxes[j];
+            let distance = pos1.position.distance_to(&pos2.position);
+            let dist = Distance {
+                ids: (pos1.id + 1, pos2.id + 1),
+                value: distance,
+            };
+            distances.push(dist);
+        }
+        // one update per row of pairs
+        progress.advance((boxes.len() - i - 1) as u64);
+    }
+    progress.finish();
+    distances
+}
+
+pub fn parse_boxes(input: &str) -> Vec<JunctionBox> {
+    let mut boxes: Vec<JunctionBox> = Vec::new();
+    for (id, line) in input.lines().enumerate() {
+        let position = Position::from_str(line);
+        boxes.push(JunctionBox {
+            id: id as u32,
+            position: position,
+        });
+    }
+    boxes
+}
+
+// Closest `connections` pairs, sorted shortest first
+pub fn closest_pairs(mut distances: Vec<Distance>, connections: usize) -> Vec<Distance> {
+    // Sot by distance
+    distances.sort_by_key(|dist| dist.value);
+    distances.truncate(connections);
+    distances
+}
+
+// Group the ids into circuits joined by the given pairs
+pub fn build_circuits(distances: &[Distance]) -> Vec<HashSet<u32>> {
+    let mut results: Vec<HashSet<u32>> = Vec::new();
+    let mut visited: HashSet<u32> = HashSet::new();
+
+    for dist in distances.iter() {
+        let (id_a, id_b): (u32, u32) = dist.ids;
+
+        // If we have visited both continue
+        if visited.contains(&id_a) && visited.contains(&id_b) {
+            continue;
+        }
+
+        let mut set: HashSet<u32> = HashSet::new();
+
+        //insert ids
+        set.insert(id_a);
+        set.insert(id_b);
+
+        let mut new_match_found = true;
+
+        //Recurse to ensure we capture all possible variants
+        while new_match_found {
+            new_match_found = false;
+
+            for second_dist in distances.iter() {
+                let (second_a, second_b) = second_dist.ids;
+
+                if set.contains(&second_a) || set.contains(&second_b) {
+                    if set.insert(second_a) {
+                        new_match_found = true;
+                    }
+                    if set.insert(second_b) {
+                        new_match_found = true;
+                    }
+                }
+            }
+        }
+
+        // Once we have exhausted our search
+        if !set.is_empty() {
+            for id in set.iter() {
+                visited.insert(*id);
+            }
+            results.push(set);
+        };
+    }
+
+    results
+}
+
+// Circuit sizes, largest first
+pub fn circuit_sizes(circuits: &[HashSet<u32>]) -> Vec<u128> {
+    let mut totals: Vec<u128> = circuits.iter().map(|set| set.len() as u128).collect();
+    totals.sort();
+    totals.reverse();
+    totals
+}
+
+// Product of the three largest circuits after joining the closest `connections` pairs
+pub fn solve_with_connections(input: &str, connections: usize) -> u128 {
+    let boxes = parse_boxes(input);
+    let distances = closest_pairs(generate_distances(&boxes, &mut NoProgress), connections);
+    let mut totals = circuit_sizes(&build_circuits(&distances));
+    totals.truncate(3);
+    totals.iter().product()
+}
+
+pub fn solve(input: &str) -> u128 {
+    solve_with_connections(input, CONNECTIONS)
+}
+
+type IdPair = (u32, u32);
+
+fn id_match(a: IdPair, b: IdPair) -> bool {
+    // Check if the first element either element of b
+    (a.0 == b.0) || (a.0 == b.1) ||
+    // Check if the second element matches either element of b
+    (a.1 == b.0) || (a.1 == b.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_from_str() {
+        let pos = Position::from_str("123,456,789");
+        let expected = Position {
+            x: 123,
+            y: 456,
+            z: 789,
+        };
+        assert_eq!(pos, expected);
+    }
+    #[test]
+    fn test_distance_to() {
+        let position1 = Position { x: 0, y: 0, z: 0 };
+        let position2 = Position { x: 4, y: 4, z: 4 };
+        let distance = position1.distance_to(&position2);
+        assert_eq!(distance, 48);
+    }
+    #[test]
+    fn test_id_match() {
+        assert!(id_match((0, 5), (1, 5)));
+        assert_ne!((0, 5), (10, 11));
+    }
+    #[test]
+    fn test_generate_distances() {
+        let boxes = vec![
+            JunctionBox {
+                id: 0,
+                position: Position { x: 0, y: 0, z: 0 },
+            },
+            JunctionBox {
+                id: 1,
+                position: Position { x: 1, y: 0, z: 0 },
+            },
+            JunctionBox {
+                id: 2,
+                position: Position { x: 0, y: 2, z: 0 },
+            },
+        ];
+        let distances = generate_distances(&boxes, &mut NoProgress);
+        let pairs: Vec<(IdPair, i64)> = distances.iter().map(|d| (d.ids, d.value)).collect();
+        assert_eq!(pairs, vec![((1, 2), 1), ((1, 3), 4), ((2, 3), 5)]);
+    }
+
+    #[test]
+    fn test_solve_with_connections() {
+        let input =
+            std::fs::read_to_string("./input_short.txt").expect("Failed to read input file");
+        assert_eq!(solve_with_connections(&input, 10), 40);
+    }
+}
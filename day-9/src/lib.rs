@@ -0,0 +1,519 @@
+use std::collections::HashSet;
+
+use aoc_common::image::{Image, Rgb};
+use aoc_common::progress::{NoProgress, Progress};
+use aoc_common::terminal::{Cell, Colour, Frame, Scale};
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Position {
+    pub x: u32,
+    pub y: u32,
+}
+
+impl Position {
+    fn from_str(position_str: &str) -> Self {
+        let parts: Vec<&str> = position_str.split(',').collect();
+        let values: Vec<u32> = parts
+            .iter()
+            .map(|s| s.parse::<u32>().expect("Invalid number"))
+            .collect();
+        Self {
+            x: values[0],
+            y: values[1],
+        }
+    }
+
+    //
+    pub fn find_area(&self, other: &Position) -> i64 {
+        let dx = self.x.abs_diff(other.x) as i64;
+        let dy = self.y.abs_diff(other.y) as i64;
+
+        if dx == 0 {
+            return dy + 1;
+        };
+
+        if dy == 0 {
+            return dx + 1;
+        };
+
+        (dx + 1) * (dy + 1)
+    }
+
+    //
+    pub fn tile_to_inclusive(&self, other: &Position) -> Vec<Position> {
+        let dx = self.x.abs_diff(other.x) as i64;
+        let dy = self.y.abs_diff(other.y) as i64;
+
+        let mut output: Vec<Position> = Vec::new();
+        //Same column
+        if dx == 0 {
+            let x = &self.x;
+
+            // If reversed then we need to reverse the output
+            let reversed = self.y > other.y;
+
+            let y_start = self.y.min(other.y);
+            let y_end = self.y.max(other.y);
+
+            for y in y_start..=y_end {
+                output.push(Position { x: *x, y: y });
+            }
+
+            if reversed {
+                output.reverse();
+            };
+        };
+        //Same Row
+        if dy == 0 {
+            let y = &self.y;
+
+            // If reversed then we need to reverse the output
+            let reversed = self.x > other.x;
+
+            let x_start = self.x.min(other.x);
+            let x_end = self.x.max(other.x);
+
+            for x in x_start..=x_end {
+                output.push(Position { x: x, y: *y });
+            }
+
+            if reversed {
+                output.reverse();
+            };
+        };
+        output
+    }
+}
+
+#[derive(Debug)]
+pub struct Rectangle {
+    pub position1: Position,
+    pub position2: Position,
+    pub area: i64,
+}
+
+impl Rectangle {
+    pub fn get_positions(&self, corners_only: bool) -> Vec<Position> {
+        let min_x = self.position1.x.min(self.position2.x);
+        let max_x = self.position1.x.max(self.position2.x);
+        let min_y = self.position1.y.min(self.position2.y);
+        let max_y = self.position1.y.max(self.position2.y);
+
+        let mut points: Vec<Position> = Vec::new();
+
+        if corners_only {
+            points.push(Position { x: min_x, y: min_y });
+            points.push(Position { x: max_x, y: min_y });
+            points.push(Position { x: min_x, y: max_y });
+            points.push(Position { x: max_x, y: max_y });
+
+            return points;
+        } else {
+            //Top Edge (min_y): x goes from min_x to max_x
+            for x in min_x..=max_x {
+                points.push(Position { x, y: min_y });
+            }
+
+            // Bottom Edge (max_y): x goes from min_x to max_x
+            for x in min_x..=max_x {
+                points.push(Position { x, y: max_y });
+            }
+
+            // Left Edge (min_x): y goes from min_y to max_y
+            for y in min_y..=max_y {
+                points.push(Position { x: min_x, y });
+            }
+
+            //Right Edge (max_x): y goes from min_y to max_y
+            for y in min_y..=max_y {
+                points.push(Position { x: max_x, y });
+            }
+
+            //Remove duplicated
+            let unique_points: HashSet<Position> = points.into_iter().collect();
+            return unique_points.into_iter().collect();
+        }
+    }
+}
+
+pub fn fill_in_tiles(red_tiles: &Vec<Position>) -> HashSet<Position> {
+    let mut set: HashSet<Position> = HashSet::new();
+    for (idx, _tile) in red_tiles.iter().enumerate().skip(1) {
+        let tile1 = &red_tiles[idx - 1];
+        let tile2 = &red_tiles[idx];
+
+        let filled_in = tile1.tile_to_inclusive(tile2);
+        for tile in filled_in.iter() {
+            set.insert(tile.clone());
+        }
+    }
+    // last tile wraps round
+    let last_tile = &red_tiles.last().unwrap();
+    let first_tile = &red_tiles.first().unwrap();
+
+    let filled_in = last_tile.tile_to_inclusive(first_tile);
+
+    for tile in filled_in.iter() {
+        set.insert(tile.clone());
+    }
+
+    set
+}
+
+#[derive(Debug)]
+pub struct Bounds {
+    pub min_x: Option<u32>,
+    pub max_x: Option<u32>,
+    pub min_y: Option<u32>,
+    pub max_y: Option<u32>,
+}
+
+impl Bounds {
+    pub fn new() -> Self {
+        Self {
+            min_x: None,
+            max_x: None,
+            min_y: None,
+            max_y: None,
+        }
+    }
+
+    pub fn update(&mut self, position: &Position) {
+        self.min_x = match self.min_x {
+            Some(current_min) => Some(current_min.min(position.x)),
+            None => Some(position.x),
+        };
+
+        self.max_x = match self.max_x {
+            Some(current_max) => Some(current_max.max(position.x)),
+            None => Some(position.x),
+        };
+
+        self.min_y = match self.min_y {
+            Some(current_min) => Some(current_min.min(position.y)),
+            None => Some(position.y),
+        };
+
+        self.max_y = match self.max_y {
+            Some(current_max) => Some(current_max.max(position.y)),
+            None => Some(position.y),
+        };
+    }
+}
+
+impl Default for Bounds {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn check_point_is_inside(
+    position: &Position,
+    bounds: &Bounds,
+    outer_set: &HashSet<Position>,
+) -> bool {
+    //early check if already on boundary then must be inside
+    if outer_set.contains(position) {
+        return true;
+    }
+    //ray casting
+    let max_x = bounds.max_x.expect("Should be populated!") + 1;
+
+    let mut crossings = 0;
+
+    let mut x = position.x;
+
+    while x <= max_x {
+        let test = Position { x, y: position.y };
+
+        // Crossing logic: Count *only when transitioning from empty → boundary*
+        if outer_set.contains(&test) {
+            // skip over horizontal segments
+            let mut x2 = x + 1;
+            while x2 <= max_x {
+                let next = Position {
+                    x: x2,
+                    y: position.y,
+                };
+                if !outer_set.contains(&next) {
+                    break;
+                }
+                x2 += 1;
+            }
+
+            crossings += 1;
+            x = x2; // jump over horizontal segment
+        } else {
+            x += 1;
+        }
+    }
+
+    crossings % 2 == 1
+}
+
+pub fn check_rectangle_is_inside(
+    rectangle: &Rectangle,
+    bounds: &Bounds,
+    outer_set: &HashSet<Position>,
+    valid_set: &mut HashSet<Position>,
+    invalid_set: &mut HashSet<Position>,
+) -> bool {
+    //ray casting
+    let positions = rectangle.get_positions(false);
+    // println!("");
+    // println!("Postions: {:?}", positions);
+    // println!("");
+    for position in positions {
+        if valid_set.contains(&position) {
+            continue;
+        }
+        if invalid_set.contains(&position) {
+            return false;
+        }
+
+        let result = check_point_is_inside(&position, bounds, &outer_set);
+
+        if !result {
+            //println!("position is invalid: {:?}", position);
+            invalid_set.insert(position);
+            return false;
+        } else {
+            //println!("position is valid: {:?}", position);
+            valid_set.insert(position);
+        }
+    }
+    true
+}
+
+// Rectangles must be sorted largest first, returns the first one fully inside the shape
+pub fn find_largest_valid_rectangle<'a>(
+    rectangles: &'a [Rectangle],
+    bounds: &Bounds,
+    outer_set: &HashSet<Position>,
+    progress: &mut dyn Progress,
+) -> Option<&'a Rectangle> {
+    let mut valid_set = outer_set.clone();
+    let mut invalid_set: HashSet<Position> = HashSet::new();
+
+    let mut found = None;
+    for rectangle in rectangles.iter() {
+        progress.advance(1);
+        if check_rectangle_is_inside(
+            rectangle,
+            bounds,
+            outer_set,
+            &mut valid_set,
+            &mut invalid_set,
+        ) {
+            found = Some(rectangle);
+            break;
+        }
+    }
+    progress.finish();
+    found
+}
+
+// Red tiles in input order, along with the bounds they cover
+pub fn parse_tiles(input: &str) -> (Vec<Position>, Bounds) {
+    let mut tiles: Vec<Position> = Vec::new();
+    let mut outer_bounds: Bounds = Bounds::new();
+    for line in input.lines() {
+        let position = Position::from_str(line);
+        outer_bounds.update(&position);
+        tiles.push(position);
+    }
+    (tiles, outer_bounds)
+}
+
+// Every rectangle with red tiles in opposite corners, largest first
+pub fn build_rectangles(tiles: &[Position]) -> Vec<Rectangle> {
+    let mut rectangles: Vec<Rectangle> = Vec::new();
+    for i in 0..tiles.len() {
+        for j in (i + 1)..tiles.len() {
+            let pos1 = &tiles[i];
+            let pos2 = &tiles[j];
+            let area = pos1.find_area(&pos2);
+            let dist = Rectangle {
+                position1: pos1.clone(),
+                position2: pos2.clone(),
+                area: area,
+            };
+            rectangles.push(dist);
+        }
+    }
+
+    // Sot by area
+    rectangles.sort_by_key(|rect| rect.area);
+    rectangles.reverse();
+    rectangles
+}
+
+// Area of the largest rectangle that fits inside the shape, 0 if none does
+pub fn solve(input: &str) -> i64 {
+    let (tiles, outer_bounds) = parse_tiles(input);
+    let outer_set = fill_in_tiles(&tiles);
+    let rectangles = build_rectangles(&tiles);
+
+    find_largest_valid_rectangle(&rectangles, &outer_bounds, &outer_set, &mut NoProgress)
+        .map(|rectangle| rectangle.area)
+        .unwrap_or(0)
+}
+
+// Scale the shape down to fit max_width x max_height, red tiles as # joined by green lines,
+// with the chosen rectangle highlighted
+pub fn render_shape(
+    tiles: &[Position],
+    rectangle: Option<&Rectangle>,
+    max_width: usize,
+    max_height: usize,
+) -> Frame {
+    let mut bounds = Bounds::new();
+    for tile in tiles {
+        bounds.update(tile);
+    }
+    let (Some(min_x), Some(max_x), Some(min_y), Some(max_y)) =
+        (bounds.min_x, bounds.max_x, bounds.min_y, bounds.max_y)
+    else {
+        return Frame::new(0, 0);
+    };
+
+    let scale = Scale::fit(
+        (min_x as u64, min_y as u64),
+        (max_x as u64, max_y as u64),
+        max_width,
+        max_height,
+    );
+    let to_cell = |position: &Position| scale.to_cell(position.x as u64, position.y as u64);
+    let mut frame = scale.frame();
+
+    // last tile wraps round to the first
+    for (idx, tile) in tiles.iter().enumerate() {
+        let next = &tiles[(idx + 1) % tiles.len()];
+        frame.draw_line(to_cell(tile), to_cell(next), Cell::new('X', Colour::Green));
+    }
+    for tile in tiles {
+        let (col, row) = to_cell(tile);
+        frame.set(col, row, Cell::new('#', Colour::Red));
+    }
+
+    if let Some(rectangle) = rectangle {
+        frame.highlight_rect(to_cell(&rectangle.position1), to_cell(&rectangle.position2));
+    }
+    frame
+}
+
+// Background, green lines, red tiles and the highlighted rectangle
+const IMAGE_PALETTE: [Rgb; 4] = [
+    [250, 250, 250],
+    [40, 140, 60],
+    [210, 40, 40],
+    [250, 200, 60],
+];
+
+// Same picture as render_shape, one pixel per cell
+pub fn shape_image(tiles: &[Position], rectangle: Option<&Rectangle>, max_size: usize) -> Image {
+    let frame = render_shape(tiles, rectangle, max_size, max_size);
+    Image::from_fn(
+        frame.width,
+        frame.height,
+        IMAGE_PALETTE.to_vec(),
+        |x, y| match frame.get(x, y) {
+            Some(cell) if cell.symbol == '#' => 2,
+            Some(cell) if cell.symbol == 'X' => 1,
+            Some(cell) if cell.highlighted => 3,
+            _ => 0,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile_to_inclusive() {
+        let position1 = Position { x: 1, y: 5 };
+        let position2 = Position { x: 1, y: 7 };
+        let output = position1.tile_to_inclusive(&position2);
+        let expected = vec![
+            Position { x: 1, y: 5 },
+            Position { x: 1, y: 6 },
+            Position { x: 1, y: 7 },
+        ];
+        assert_eq!(output, expected);
+
+        let position1 = Position { x: 1, y: 7 };
+        let position2 = Position { x: 1, y: 5 };
+        let output = position1.tile_to_inclusive(&position2);
+        let expected = vec![
+            Position { x: 1, y: 7 },
+            Position { x: 1, y: 6 },
+            Position { x: 1, y: 5 },
+        ];
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_find_largest_valid_rectangle() {
+        let input =
+            std::fs::read_to_string("./input_short.txt").expect("Failed to read input file");
+        let (tiles, bounds) = parse_tiles(&input);
+        let outer_set = fill_in_tiles(&tiles);
+        let rectangles = build_rectangles(&tiles);
+
+        let found = find_largest_valid_rectangle(&rectangles, &bounds, &outer_set, &mut NoProgress)
+            .expect("Should find a rectangle");
+        assert_eq!(found.area, 24);
+    }
+
+    #[test]
+    fn test_solve() {
+        let input =
+            std::fs::read_to_string("./input_short.txt").expect("Failed to read input file");
+        assert_eq!(solve(&input), 24);
+    }
+
+    #[test]
+    fn test_render_shape() {
+        let input =
+            std::fs::read_to_string("./input_short.txt").expect("Failed to read input file");
+        let (tiles, _bounds) = parse_tiles(&input);
+        let rectangle = Rectangle {
+            position1: Position { x: 9, y: 5 },
+            position2: Position { x: 2, y: 3 },
+            area: 24,
+        };
+
+        let frame = render_shape(&tiles, Some(&rectangle), 80, 40);
+        assert_eq!(
+            frame.to_plain(),
+            "     #XXX#\n     X   X\n#XXXX#   X\nX        X\n#XXXXXX# X\n       X X\n       #X#\n"
+        );
+        assert!(frame.get(0, 2).unwrap().highlighted);
+        assert!(frame.get(7, 4).unwrap().highlighted);
+        assert!(!frame.get(8, 4).unwrap().highlighted);
+
+        // scaled down to fit
+        let frame = render_shape(&tiles, None, 5, 5);
+        assert_eq!((frame.width, frame.height), (5, 4));
+    }
+
+    #[test]
+    fn test_shape_image() {
+        let input =
+            std::fs::read_to_string("./input_short.txt").expect("Failed to read input file");
+        let (tiles, _bounds) = parse_tiles(&input);
+        let rectangle = Rectangle {
+            position1: Position { x: 9, y: 5 },
+            position2: Position { x: 2, y: 3 },
+            area: 24,
+        };
+
+        let image = shape_image(&tiles, Some(&rectangle), 100);
+        assert_eq!((image.width, image.height), (10, 7));
+        assert_eq!(image.get(5, 0), 2);
+        assert_eq!(image.get(6, 0), 1);
+        assert_eq!(image.get(1, 3), 3);
+        assert_eq!(image.get(0, 0), 0);
+    }
+}